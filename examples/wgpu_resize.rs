@@ -0,0 +1,46 @@
+//! Shows when a `wgpu` surface should be reconfigured.
+//!
+//! `wgpu` is not a dependency of this crate, so the surface calls are left as comments.
+
+use winit::event_loop::EventLoop;
+use winit::window::WindowBuilder;
+use winit_event_helper::*;
+
+fn main() {
+    let event_loop = EventLoop::new();
+    let window = WindowBuilder::new().build(&event_loop).unwrap();
+
+    // let surface = unsafe { instance.create_surface(&window) }.unwrap();
+    // let size = window.inner_size();
+    // let mut config = wgpu::SurfaceConfiguration { width: size.width, height: size.height, .. };
+
+    #[cfg(feature = "unique_windows")]
+    let window_id = window.id();
+
+    let mut eh = EventHelper::new(());
+    // keeps the window alive and lets callbacks reach it through `eh.window(id)`
+    eh.register_window(window);
+    let callbacks = Callbacks::<()>::empty();
+
+    event_loop.run(move |event, _, control_flow| {
+        // exits when the window is closed or destroyed
+        if !eh.update_with_control_flow(&callbacks, &event, control_flow) {
+            return;
+        }
+
+        // only fires for non-zero sizes, so minimizing the window does not break the surface
+        #[cfg(not(feature = "unique_windows"))]
+        let reconfigure = eh.surface_needs_reconfigure();
+        #[cfg(feature = "unique_windows")]
+        let reconfigure = eh.surface_needs_reconfigure(window_id);
+
+        if let Some(size) = reconfigure {
+            // config.width = size.width;
+            // config.height = size.height;
+            // surface.configure(&device, &config);
+            println!("reconfigure surface to {}x{}", size.width, size.height);
+        }
+
+        // render to the surface
+    })
+}
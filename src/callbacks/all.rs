@@ -1,26 +1,23 @@
-use winit::event::Event;
-
-#[cfg(feature = "unique_devices")]
-use winit::event::DeviceId;
+use winit::event::{DeviceEvent, DeviceId, ElementState, Event, KeyboardInput, WindowEvent};
 
 #[cfg(feature = "unique_windows")]
-use winit::event::WindowId;
-
-#[cfg(any(feature = "unique_windows", feature = "unique_devices"))]
-use ahash::AHashMap;
+use winit::window::WindowId;
 
 #[cfg(any(feature = "unique_windows", feature = "unique_devices"))]
 use crate::default_ahashmap::DefaultAHashMap;
 
 use crate::{definitions::CallbackCallable, EventHelper};
 
+#[cfg(feature = "gamepad")]
+use crate::input::{InputCallbacks, InputData};
+
 use super::{
     device::{DeviceCallbackData, DeviceCallbacks},
     general::{GeneralCallbackData, GeneralCallbacks},
     window::{WindowCallbackData, WindowCallbacks},
 };
 
-#[derive(Clone, Default)]
+#[derive(Default)]
 /// Struct that holds all the callbacks and accompanying callback data as well as a user-supplied `user_data` struct.
 ///
 /// This struct is passed to callback functions.
@@ -34,49 +31,77 @@ pub struct CallbackData {
     pub device: DeviceCallbackData,
     #[cfg(feature = "unique_devices")]
     pub devices: DefaultAHashMap<DeviceId, DeviceCallbackData>,
+    #[cfg(feature = "gamepad")]
+    /// The input data of all gamepads, filled by [EventHelper::poll_gamepads].
+    pub gamepad: InputData,
+    /// The devices that pressed a key or button this step, in order of their first press.
+    pub pressed_devices: Vec<DeviceId>,
+}
+
+impl Clone for CallbackData {
+    fn clone(&self) -> Self {
+        Self {
+            general: self.general.clone(),
+            #[cfg(not(feature = "unique_windows"))]
+            window: self.window.clone(),
+            #[cfg(feature = "unique_windows")]
+            windows: self.windows.clone(),
+            #[cfg(not(feature = "unique_devices"))]
+            device: self.device.clone(),
+            #[cfg(feature = "unique_devices")]
+            devices: self.devices.clone(),
+            #[cfg(feature = "gamepad")]
+            gamepad: self.gamepad.clone(),
+            pressed_devices: self.pressed_devices.clone(),
+        }
+    }
+
+    /// Reuses the allocations of `self`, so copying the data for the callbacks every step does not allocate.
+    fn clone_from(&mut self, source: &Self) {
+        self.general.clone_from(&source.general);
+        #[cfg(not(feature = "unique_windows"))]
+        self.window.clone_from(&source.window);
+        #[cfg(feature = "unique_windows")]
+        self.windows.clone_from(&source.windows);
+        #[cfg(not(feature = "unique_devices"))]
+        self.device.clone_from(&source.device);
+        #[cfg(feature = "unique_devices")]
+        self.devices.clone_from(&source.devices);
+        #[cfg(feature = "gamepad")]
+        self.gamepad.clone_from(&source.gamepad);
+        self.pressed_devices.clone_from(&source.pressed_devices);
+    }
 }
 
 impl CallbackData {
     /// Calls the callbacks associated with this struct and child structs.
     ///
     /// This is called once internally after every step, but the user can call it manually.
-    pub fn call_callbacks<D>(self, event_helper: &mut EventHelper<D>, callbacks: &Callbacks<D>) {
+    pub fn call_callbacks<D, E: 'static>(
+        &self,
+        event_helper: &mut EventHelper<D>,
+        callbacks: &Callbacks<D, E>,
+    ) {
         self.general
             .call_callbacks(event_helper, &callbacks.general);
 
+        #[cfg(not(feature = "unique_windows"))]
         self.window.call_callbacks(event_helper, &callbacks.window);
 
         #[cfg(feature = "unique_windows")]
         self.windows
-            .iter()
-            .filter_map(|(window_id, window_callback_data)| {
-                callbacks
-                    .windows
-                    .map
-                    .get(window_id)
-                    .zip(Some(window_callback_data))
-            })
-            .for_each(|(window_callbacks, window_callback_data)| {
-                window_callback_data.call_callbacks(event_helper, &window_callbacks);
-            });
+            .call_callbacks(event_helper, &callbacks.windows);
 
+        #[cfg(not(feature = "unique_devices"))]
         self.device.call_callbacks(event_helper, &callbacks.device);
 
         #[cfg(feature = "unique_devices")]
-        event_helper
-            .callback_data
-            .devices
-            .iter()
-            .filter_map(|(device_id, device_callback_data)| {
-                callbacks
-                    .devices
-                    .map
-                    .get(device_id)
-                    .zip(Some(device_callback_data))
-            })
-            .for_each(|(device_callbacks, device_callback_data)| {
-                device_callback_data.call_callbacks(event_helper, &device_callbacks);
-            });
+        self.devices
+            .call_callbacks(event_helper, &callbacks.devices);
+
+        #[cfg(feature = "gamepad")]
+        self.gamepad
+            .call_callbacks(event_helper, &callbacks.gamepad);
     }
 
     pub fn clear(&mut self) {
@@ -95,10 +120,21 @@ impl CallbackData {
         self.devices
             .values_mut()
             .for_each(DeviceCallbackData::clear);
+
+        #[cfg(feature = "gamepad")]
+        self.gamepad.clear();
+
+        self.pressed_devices.clear();
     }
 
     #[allow(unused_variables)]
     pub fn update<'a, E>(&mut self, event: &Event<'a, E>) {
+        if let Some(device_id) = pressing_device(event) {
+            if !self.pressed_devices.contains(&device_id) {
+                self.pressed_devices.push(device_id);
+            }
+        }
+
         match event {
             Event::WindowEvent { event, window_id } => {
                 #[cfg(not(feature = "unique_windows"))]
@@ -127,9 +163,57 @@ impl CallbackData {
     }
 }
 
-#[derive(Clone)]
+/// Returns the device that sent the event if it is a key or button press.
+fn pressing_device<E>(event: &Event<'_, E>) -> Option<DeviceId> {
+    match *event {
+        Event::WindowEvent {
+            event:
+                WindowEvent::KeyboardInput {
+                    device_id,
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            ..
+                        },
+                    ..
+                }
+                | WindowEvent::MouseInput {
+                    device_id,
+                    state: ElementState::Pressed,
+                    ..
+                },
+            ..
+        } => Some(device_id),
+        Event::DeviceEvent {
+            device_id,
+            event:
+                DeviceEvent::Key(KeyboardInput {
+                    state: ElementState::Pressed,
+                    ..
+                })
+                | DeviceEvent::Button {
+                    state: ElementState::Pressed,
+                    ..
+                },
+        } => Some(device_id),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "boxed_callbacks"))]
+/// A callback function for [Callbacks::raw_event]
+pub type RawEventCallback<D, E> = for<'a> fn(&mut EventHelper<D>, &Event<'a, E>);
+
+#[cfg(feature = "boxed_callbacks")]
+/// A boxed callback closure for [Callbacks::raw_event]
+pub type RawEventCallback<D, E> =
+    std::cell::RefCell<Box<dyn for<'a> FnMut(&mut EventHelper<D>, &Event<'a, E>)>>;
+
+#[cfg_attr(not(feature = "boxed_callbacks"), derive(Clone))]
 /// A collection of callbacks. This is the only `callbacks` type struct you should use directly.
-pub struct Callbacks<D> {
+///
+/// `E` is the user event type of the event loop, which only matters for [Callbacks::raw_event].
+pub struct Callbacks<D, E: 'static = ()> {
     pub general: GeneralCallbacks<D>,
     #[cfg(not(feature = "unique_windows"))]
     pub window: WindowCallbacks<D>,
@@ -139,24 +223,160 @@ pub struct Callbacks<D> {
     pub device: DeviceCallbacks<D>,
     #[cfg(feature = "unique_devices")]
     pub devices: DefaultAHashMap<DeviceId, DeviceCallbacks<D>>,
+    #[cfg(feature = "gamepad")]
+    pub gamepad: InputCallbacks<D>,
+    pub raw_event: Option<RawEventCallback<D, E>>,
 }
 
-impl<D> Default for Callbacks<D> {
+impl<D, E: 'static> Default for Callbacks<D, E> {
     fn default() -> Self {
         Self {
             general: Default::default(),
+            #[cfg(not(feature = "unique_windows"))]
             window: Default::default(),
             #[cfg(feature = "unique_windows")]
             windows: Default::default(),
+            #[cfg(not(feature = "unique_devices"))]
             device: Default::default(),
             #[cfg(feature = "unique_devices")]
             devices: Default::default(),
+            #[cfg(feature = "gamepad")]
+            gamepad: Default::default(),
+            raw_event: None,
         }
     }
 }
 
-impl<D> Callbacks<D> {
+impl<D, E: 'static> Callbacks<D, E> {
     pub fn empty() -> Self {
         Self::default()
     }
+
+    #[cfg(not(feature = "boxed_callbacks"))]
+    /// Sets a callback that is called with every event passed to [EventHelper::update],
+    /// before the event is added to the [CallbackData].
+    ///
+    /// This is an escape hatch for events and fields that are not covered by the other callbacks.
+    /// Unlike those, it runs on the original event instead of the data of a whole step,
+    /// so it is called multiple times per step.
+    pub fn raw_event(&mut self, callback: RawEventCallback<D, E>) {
+        self.raw_event = Some(callback);
+    }
+
+    #[cfg(feature = "boxed_callbacks")]
+    /// Sets a callback that is called with every event passed to [EventHelper::update],
+    /// before the event is added to the [CallbackData].
+    ///
+    /// This is an escape hatch for events and fields that are not covered by the other callbacks.
+    /// Unlike those, it runs on the original event instead of the data of a whole step,
+    /// so it is called multiple times per step.
+    pub fn raw_event(
+        &mut self,
+        callback: impl for<'a> FnMut(&mut EventHelper<D>, &Event<'a, E>) + 'static,
+    ) {
+        self.raw_event = Some(std::cell::RefCell::new(Box::new(callback)));
+    }
+
+    /// Removes all callbacks
+    pub fn clear_all(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Removes all input callbacks of windows and devices, keeping all other callbacks
+    pub fn clear_inputs(&mut self) {
+        #[cfg(not(feature = "unique_windows"))]
+        clear_window_inputs(&mut self.window);
+
+        #[cfg(feature = "unique_windows")]
+        self.windows.values_mut().for_each(clear_window_inputs);
+
+        #[cfg(not(feature = "unique_devices"))]
+        {
+            self.device.inputs = Default::default();
+        }
+
+        #[cfg(feature = "unique_devices")]
+        self.devices
+            .values_mut()
+            .for_each(|device| device.inputs = Default::default());
+
+        #[cfg(feature = "gamepad")]
+        {
+            self.gamepad = Default::default();
+        }
+    }
+
+    /// Removes all window callbacks
+    pub fn clear_window_callbacks(&mut self) {
+        #[cfg(not(feature = "unique_windows"))]
+        {
+            self.window = Default::default();
+        }
+
+        #[cfg(feature = "unique_windows")]
+        self.windows.clear();
+    }
+
+    /// Removes all device callbacks
+    pub fn clear_device_callbacks(&mut self) {
+        #[cfg(not(feature = "unique_devices"))]
+        {
+            self.device = Default::default();
+        }
+
+        #[cfg(feature = "unique_devices")]
+        self.devices.clear();
+    }
+
+    /// Removes all general callbacks
+    pub fn clear_general_callbacks(&mut self) {
+        self.general = Default::default();
+    }
+}
+
+fn clear_window_inputs<D>(window: &mut WindowCallbacks<D>) {
+    window.inputs = Default::default();
+
+    #[cfg(feature = "windows_with_device_ids")]
+    {
+        window.inputs_with_id = Default::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::{
+        dpi::PhysicalSize,
+        event::{ElementState, VirtualKeyCode},
+    };
+
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn clear_inputs_keeps_other_callbacks() {
+        let mut eh = EventHelper::new(0);
+        let mut callbacks = Callbacks::<i32>::empty();
+        callbacks
+            .window
+            .inputs
+            .just_pressed(VirtualKeyCode::Space, |eh| **eh += 1);
+        callbacks.window.resized(|eh, _| **eh += 10);
+
+        callbacks.clear_inputs();
+
+        testing::feed_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::Space,
+            ElementState::Pressed,
+        );
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::Resized(PhysicalSize::new(800, 600)),
+        );
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 10);
+    }
 }
@@ -3,7 +3,7 @@ use winit::event::{AxisId, DeviceEvent, KeyboardInput, MouseButton};
 use crate::{
     create_callbacks,
     definitions::{LineDelta, PixelDelta},
-    input::data::InputData,
+    input::{axes::AxisValues, data::InputData},
 };
 
 create_callbacks! {
@@ -18,10 +18,23 @@ create_callbacks! {
         clr vec pub mouse_wheel: (LineDelta, PixelDelta),
         clr vec pub motion: (AxisId, f64),
         clr cus pub inputs: InputData,
+        clr cus pub axes: AxisValues,
     }
 }
 
 impl DeviceCallbackData {
+    /// Returns the latest value of the given axis, see [AxisValues].
+    pub fn axis_value(&self, axis: AxisId) -> Option<f64> {
+        self.axes.value(axis)
+    }
+
+    /// Returns the raw line and pixel deltas scrolled this step, summed separately.
+    ///
+    /// Same as [InputData::scroll_delta] on [DeviceCallbackData::inputs].
+    pub fn scroll_accumulated(&self) -> (LineDelta, PixelDelta) {
+        self.inputs.scroll_delta()
+    }
+
     pub fn update(&mut self, event: &DeviceEvent) {
         match event {
             &DeviceEvent::Key(KeyboardInput {
@@ -46,9 +59,11 @@ impl DeviceCallbackData {
                     delta.try_into().unwrap_or_default(),
                     delta.try_into().unwrap_or_default(),
                 ));
+                self.inputs.update_scroll(delta);
             }
             &DeviceEvent::Motion { axis, value } => {
                 self.motion.push((axis, value));
+                self.axes.update(axis, value);
             }
             DeviceEvent::Added => self.added = true,
             DeviceEvent::Removed => self.removed = true,
@@ -16,12 +16,23 @@ create_callbacks! {
         clr boo pub resumed: bool,
         clr boo pub redraw_events_cleared: bool,
         clr boo pub loop_destroyed: bool,
+        /// Set on the first step only, e.g. for creating resources once the event loop has started.
+        clr boo pub first_update: bool,
         clr opt pub new_events: StartCause,
+        /// The windows that requested a redraw this step, each included once.
+        ///
+        /// The `redraw_requested` callback activates once per step with all of them,
+        /// while callbacks added through `redraw_requested_with_key` only activate for their own window.
         clr set pub redraw_requested: WindowId,
     }
 }
 
 impl GeneralCallbackData {
+    /// Returns the windows that requested a redraw this step, each once
+    pub fn redraw_requested_iter(&self) -> impl ExactSizeIterator<Item = WindowId> + '_ {
+        self.redraw_requested.iter().copied()
+    }
+
     pub fn update<'a, E>(&mut self, event: &Event<'a, E>) {
         match event {
             Event::LoopDestroyed => self.loop_destroyed = true,
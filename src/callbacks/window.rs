@@ -1,18 +1,31 @@
-use std::path::PathBuf;
+use std::{
+    cell::Cell,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use ahash::AHashMap;
 
 use winit::{
-    dpi::{PhysicalPosition, PhysicalSize},
-    event::{AxisId, Ime, KeyboardInput, WindowEvent, TouchPhase},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    event::{
+        AxisId, ElementState, Ime, KeyboardInput, MouseButton, MouseScrollDelta, TouchPhase,
+        VirtualKeyCode, WindowEvent,
+    },
     window::Theme,
 };
 
 use crate::{
     create_callbacks,
-    definitions::{CursorState, LineDelta, PixelDelta, QuitWindow},
-    input::data::InputData,
+    definitions::{CursorState, LineDelta, PixelDelta, QuitWindow, SwipeDirection},
+    input::{axes::AxisValues, data::InputData, BindingHandle},
+    touch::data::TouchData,
     IdLessTouch,
 };
 
+#[cfg(feature = "windows_with_device_ids")]
+use winit::event::DeviceId;
+
 #[cfg(feature = "windows_with_device_ids")]
 use crate::input::data::InputDataWithId;
 
@@ -35,9 +48,19 @@ create_callbacks! {
         clr opt pub theme: Theme,
         clr opt pub hover_cancelled: bool,
         clr opt pub mouse_wheel: (LineDelta, PixelDelta),
+        /// Set if the net scroll ticks this step are positive, see [WindowCallbackData::scroll_ticks].
+        clr boo pub scrolled_up: bool,
+        /// Set if the net scroll ticks this step are negative, see [WindowCallbackData::scroll_ticks].
+        clr boo pub scrolled_down: bool,
+        /// The whole scroll ticks this step.
+        clr dat pub scroll_tick_count: i32,
+        /// The fractional scroll ticks carried over to the next step.
+        ign dat pub scroll_tick_remainder: f64,
         clr opt pub smart_magnify: usize,
         clr vec pub text: char,
         clr vec pub ime: Ime,
+        /// The IME composition state, see [WindowCallbackData::ime_preedit] and [WindowCallbackData::ime_commits].
+        clr cus pub ime_state: ImeState,
         clr vec pub touch: IdLessTouch,
         clr vec pub touchpad_pressure: (i64, f32),
         clr vec pub touchpad_magnify: (f64, TouchPhase),
@@ -45,7 +68,68 @@ create_callbacks! {
         clr vec pub axis_motion: (AxisId, f64),
         clr set pub hovered_files: PathBuf,
         clr set pub dropped_files: PathBuf,
+        /// The files dropped this step in the order they were dropped, without duplicates.
+        clr vec pub dropped_files_ordered: PathBuf,
         clr cus pub inputs: InputData,
+        clr cus pub touches: TouchData,
+        clr cus pub axes: AxisValues,
+        /// The instant and size of the most recent resize, used by [WindowCallbacks::resized_settled].
+        ign cus pub resize_settle: ResizeSettle,
+        /// The instant of the most recent [WindowEvent::Moved], kept across steps.
+        ign dat pub last_moved_at: Option<Instant>,
+        /// The last known cursor position, kept across steps.
+        ///
+        /// Reset to `None` when the cursor leaves the window.
+        ign dat pub cursor_position: Option<PhysicalPosition<f64>>,
+        /// The last known scale factor, kept across steps.
+        ign dat pub current_scale_factor: Option<f64>,
+        /// Whether the window lost focus, kept across steps.
+        ///
+        /// Windows are assumed to be focused until a [WindowEvent::Focused] event says otherwise.
+        ign dat pub unfocused: bool,
+        /// Whether the cursor is inside the window, kept across steps.
+        ///
+        /// winit only reports the cursor entering and leaving, so this is `false` at startup
+        /// until the cursor enters or moves, even if it already was inside the window.
+        ign dat pub cursor_inside: bool,
+        /// Whether the window is fully hidden from view, kept across steps.
+        ign dat pub currently_occluded: bool,
+        /// Whether all pressed inputs are forgotten when the window loses focus, see [InputData::forget].
+        ///
+        /// Off by default. Turning it on is recommended, as the release of an input that is held
+        /// while the window loses focus is delivered to another window, leaving the input stuck as pressed.
+        ign dat pub release_on_unfocus: bool,
+        /// The cursor position from before the first cursor movement this step.
+        clr dat pub cursor_step_origin: Option<PhysicalPosition<f64>>,
+        /// The path length in physical pixels the cursor travelled this step.
+        clr dat pub cursor_travel: f64,
+        /// Whether every cursor position is stored in [WindowCallbackData::cursor_path].
+        ///
+        /// Off by default, as most applications only need the final position of a step.
+        ign dat pub track_cursor_path: bool,
+        /// Every cursor position this step in the order they were received, if [WindowCallbackData::track_cursor_path] is set.
+        clr dat pub cursor_path_positions: Vec<PhysicalPosition<f64>>,
+        /// The button and cursor position of the most recent mouse button press, kept across steps, see [WindowCallbackData::last_click].
+        ign dat pub last_button_press: Option<(MouseButton, PhysicalPosition<f64>)>,
+        /// The buttons that started dragging this step.
+        clr set pub drag_started: MouseButton,
+        /// The cursor movement since the button went down, for every button that is dragging and moved this step.
+        clr map pub drag_delta: MouseButton => PhysicalPosition<f64>,
+        /// The buttons that stopped dragging this step.
+        clr set pub drag_ended: MouseButton,
+        ign dat pub drags: DragTracker,
+        /// The summed touchpad magnification deltas of the current or most recent gesture, kept across steps.
+        ign dat pub magnify_gesture_delta: f64,
+        /// Whether a touchpad magnification gesture is in progress, kept across steps.
+        ign dat pub magnify_gesture_active: bool,
+        /// The summed touchpad rotation deltas in degrees of the current or most recent gesture, kept across steps.
+        ign dat pub rotate_gesture_delta: f32,
+        #[cfg(feature="windows_with_device_ids")]
+        /// The summed touchpad rotation deltas in degrees of the current or most recent gesture of every device, kept across steps.
+        ign dat pub rotate_gesture_delta_with_id: AHashMap<DeviceId, f32>,
+        #[cfg(feature="diagnostics")]
+        /// The number of events of every kind received this step, by [WindowEvent] variant name.
+        clr dat pub event_count_map: AHashMap<&'static str, u32>,
         #[cfg(feature="windows_with_device_ids")]
         clr map pub cursor_entered_with_id: DeviceId => Option<bool>,
         #[cfg(feature="windows_with_device_ids")]
@@ -65,22 +149,210 @@ create_callbacks! {
         #[cfg(feature="windows_with_device_ids")]
         clr map pub axis_motion_with_id: DeviceId => Vec<(AxisId, f64)>,
         #[cfg(feature="windows_with_device_ids")]
-        clr cus pub inputs_with_id: InputDataWithId<D>,
+        clr cus pub inputs_with_id: InputDataWithId,
     }
 }
 
 impl WindowCallbackData {
+    /// Returns the number of fingers and their direction if a multi-finger swipe ended this step.
+    ///
+    /// See [TouchData::multi_finger_swipe].
+    pub fn multi_finger_swipe(&self) -> Option<(u32, SwipeDirection)> {
+        self.touches.multi_finger_swipe()
+    }
+
+    /// Returns the latest value of the given axis, see [AxisValues].
+    pub fn axis_value(&self, axis: AxisId) -> Option<f64> {
+        self.axes.value(axis)
+    }
+
+    /// Returns the whole scroll ticks this step, where positive values are scrolling up.
+    ///
+    /// Every line of a [MouseScrollDelta::LineDelta] is a tick,
+    /// and every [PIXELS_PER_SCROLL_TICK] pixels of a [MouseScrollDelta::PixelDelta] are a tick.
+    /// Fractions of a tick are carried over to the next step, so slow trackpad scrolling adds up.
+    pub fn scroll_ticks(&self) -> i32 {
+        self.scroll_tick_count
+    }
+
+    /// Returns the characters received this step as a [String].
+    pub fn text_string(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Returns the characters received this step as a [String], without control characters
+    /// such as backspace (`\u{8}`) and delete (`\u{7f}`).
+    pub fn printable_text(&self) -> String {
+        self.text.iter().filter(|c| !c.is_control()).collect()
+    }
+
+    /// Returns the direction if a single finger swiped and was lifted this step.
+    ///
+    /// See [TouchData::swipe].
+    pub fn swipe(&self) -> Option<SwipeDirection> {
+        self.touches.swipe()
+    }
+
+    /// Returns the latest touch of every finger currently touching the screen, keyed by finger id.
+    ///
+    /// See [TouchData::active_touches].
+    pub fn active_touches(&self) -> &AHashMap<u64, IdLessTouch> {
+        self.touches.active_touches()
+    }
+
+    /// Returns the button and cursor position of the most recent mouse button press, kept across steps.
+    ///
+    /// Returns `None` if no button has been pressed yet, or if the most recent press
+    /// arrived before the cursor position was known, e.g. before the first cursor movement.
+    pub fn last_click(&self) -> Option<(MouseButton, PhysicalPosition<f64>)> {
+        self.last_button_press
+    }
+
+    /// Returns the path length in physical pixels the cursor travelled this step.
+    ///
+    /// Every intermediate cursor position is taken into account, not just the start and end of the step.
+    pub fn cursor_travel_this_step(&self) -> f64 {
+        self.cursor_travel
+    }
+
+    /// Returns every cursor position this step in the order they were received,
+    /// e.g. for drawing applications that need the intermediate positions.
+    ///
+    /// Always empty unless [WindowCallbackData::track_cursor_path] is set.
+    pub fn cursor_path(&self) -> &[PhysicalPosition<f64>] {
+        &self.cursor_path_positions
+    }
+
+    /// Returns the text currently being composed by the IME, see [ImeState::preedit].
+    pub fn ime_preedit(&self) -> &str {
+        &self.ime_state.preedit
+    }
+
+    /// Returns the strings committed by the IME this step, see [ImeState::commits].
+    pub fn ime_commits(&self) -> &[String] {
+        &self.ime_state.commits
+    }
+
+    /// Returns the movement of the cursor in physical pixels this step, or `None` if it did not move.
+    ///
+    /// The movement is measured from the last known cursor position before this step,
+    /// or from the first cursor position of this step if the cursor just entered the window.
+    pub fn cursor_delta(&self) -> Option<PhysicalPosition<f64>> {
+        self.cursor_step_origin
+            .zip(self.cursor_moved)
+            .map(|(origin, position)| PhysicalPosition::new(position.x - origin.x, position.y - origin.y))
+    }
+
+    /// Returns true if the window was moved this step or less than [WINDOW_INTERACTION_QUIET_PERIOD] ago,
+    /// e.g. for pausing expensive work while the user drags the window.
+    pub fn being_moved(&self) -> bool {
+        self.moved.is_some() || within_quiet_period(self.last_moved_at)
+    }
+
+    /// Returns true if the window was resized this step or less than [WINDOW_INTERACTION_QUIET_PERIOD] ago,
+    /// e.g. for pausing expensive work while the user drags the window border.
+    ///
+    /// See [WindowCallbacks::resized_settled] for activating a callback once resizing has stopped.
+    pub fn being_resized(&self) -> bool {
+        self.resized.is_some()
+            || within_quiet_period(self.resize_settle.last_resized().map(|(instant, _)| instant))
+    }
+
+    /// Returns the last known focus state of the window, which is `true` until the window loses focus.
+    pub fn has_focus(&self) -> bool {
+        !self.unfocused
+    }
+
+    /// Returns the last known state of the cursor being inside the window, see [WindowCallbackData::cursor_inside]
+    pub fn is_cursor_inside(&self) -> bool {
+        self.cursor_inside
+    }
+
+    /// Returns the last known scale factor, or 1.0 if no scale factor change has been received yet.
+    pub fn scale_factor_or_default(&self) -> f64 {
+        self.current_scale_factor.unwrap_or(1.0)
+    }
+
+    /// Returns [WindowCallbackData::cursor_moved] in logical pixels.
+    ///
+    /// Uses [WindowCallbackData::scale_factor_or_default], so it assumes a scale factor of 1.0
+    /// until a scale factor change has been received.
+    pub fn cursor_moved_logical(&self) -> Option<LogicalPosition<f64>> {
+        self.cursor_moved
+            .map(|position| position.to_logical(self.scale_factor_or_default()))
+    }
+
+    /// Returns [WindowCallbackData::size] in logical pixels.
+    ///
+    /// Uses [WindowCallbackData::scale_factor_or_default], so it assumes a scale factor of 1.0
+    /// until a scale factor change has been received.
+    pub fn size_logical(&self) -> Option<LogicalSize<f64>> {
+        self.size
+            .map(|size| size.to_logical(self.scale_factor_or_default()))
+    }
+
+    /// Returns the summed touchpad magnification deltas since the last [TouchPhase::Started].
+    ///
+    /// The value is kept after the gesture ends until the next one starts.
+    /// A cancelled gesture is discarded and resets the value to 0.0.
+    pub fn magnify_accumulated(&self) -> f64 {
+        self.magnify_gesture_delta
+    }
+
+    /// Returns true if a touchpad magnification gesture is in progress.
+    pub fn magnify_active(&self) -> bool {
+        self.magnify_gesture_active
+    }
+
+    /// Returns the summed touchpad rotation deltas in degrees since the last [TouchPhase::Started].
+    ///
+    /// The value is not wrapped, so a full counterclockwise turn reads as 360.0 rather than 0.0.
+    /// It is kept after the gesture ends until the next one starts.
+    /// A cancelled gesture is discarded and resets the value to 0.0.
+    pub fn rotation_accumulated(&self) -> f32 {
+        self.rotate_gesture_delta
+    }
+
+    #[cfg(feature = "windows_with_device_ids")]
+    /// Same as [WindowCallbackData::rotation_accumulated], but for the given device.
+    pub fn rotation_accumulated_with_id(&self, device_id: DeviceId) -> f32 {
+        self.rotate_gesture_delta_with_id.get(&device_id).copied().unwrap_or_default()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// Returns the number of events of every kind received this step, by [WindowEvent] variant name.
+    pub fn event_counts(&self) -> &AHashMap<&'static str, u32> {
+        &self.event_count_map
+    }
+
     pub fn update(&mut self, event: &WindowEvent) {
+        #[cfg(feature = "diagnostics")]
+        {
+            *self.event_count_map.entry(window_event_name(event)).or_default() += 1;
+        }
+
         #[allow(unused_variables)]
         match event {
-            &WindowEvent::Focused(is_focused) => self.focused = Some(is_focused),
+            &WindowEvent::Focused(is_focused) => {
+                self.focused = Some(is_focused);
+                self.unfocused = !is_focused;
+
+                if !is_focused && self.release_on_unfocus {
+                    self.inputs.forget_pressed();
+
+                    #[cfg(feature = "windows_with_device_ids")]
+                    self.inputs_with_id.values_mut().for_each(InputData::forget_pressed);
+                }
+            }
             &WindowEvent::Moved(new_position) => {
                 self.moved = Some(new_position);
                 self.position = Some(new_position);
+                self.last_moved_at = Some(Instant::now());
             }
             &WindowEvent::Resized(new_size) => {
                 self.resized = Some(new_size);
                 self.size = Some(new_size);
+                self.resize_settle.last_resized = Some((Instant::now(), new_size));
             }
             &WindowEvent::MouseInput {
                 device_id,
@@ -90,6 +362,22 @@ impl WindowCallbackData {
             } => {
                 self.inputs.update(button, state);
 
+                match state {
+                    ElementState::Pressed => {
+                        // a press without a known cursor position still replaces the previous click
+                        self.last_button_press = self.cursor_position.map(|position| (button, position));
+
+                        if let Some(position) = self.cursor_position {
+                            self.drags.press(button, position);
+                        }
+                    }
+                    ElementState::Released => {
+                        if self.drags.release(button) {
+                            self.drag_ended.insert(button);
+                        }
+                    }
+                }
+
                 #[cfg(feature = "windows_with_device_ids")]
                 self.inputs_with_id
                     .entry(device_id)
@@ -142,12 +430,28 @@ impl WindowCallbackData {
                 let (lines, pixels) = self.mouse_wheel.get_or_insert(Default::default());
                 *lines += delta.try_into().unwrap_or_default();
                 *pixels += delta.try_into().unwrap_or_default();
+                self.inputs.update_scroll(delta);
+
+                self.scroll_tick_remainder += match delta {
+                    MouseScrollDelta::LineDelta(_, down) => down as f64,
+                    MouseScrollDelta::PixelDelta(position) => position.y / PIXELS_PER_SCROLL_TICK,
+                };
+                let ticks = self.scroll_tick_remainder.trunc();
+                self.scroll_tick_remainder -= ticks;
+                self.scroll_tick_count += ticks as i32;
+                self.scrolled_up = self.scroll_tick_count > 0;
+                self.scrolled_down = self.scroll_tick_count < 0;
 
                 #[cfg(feature = "windows_with_device_ids")]
                 {
                     let (lines, pixels) = self.mouse_wheel_with_id.entry(device_id).or_default();
                     *lines += delta.try_into().unwrap_or_default();
                     *pixels += delta.try_into().unwrap_or_default();
+
+                    self.inputs_with_id
+                        .entry(device_id)
+                        .or_default()
+                        .update_scroll(delta);
                 }
             }
             &WindowEvent::AxisMotion {
@@ -157,6 +461,7 @@ impl WindowCallbackData {
                 ..
             } => {
                 self.axis_motion.push((axis, value));
+                self.axes.update(axis, value);
 
                 #[cfg(feature = "windows_with_device_ids")]
                 self.axis_motion_with_id
@@ -170,20 +475,34 @@ impl WindowCallbackData {
                     .insert(QuitWindow::CLOSE_REQUESTED);
             }
             WindowEvent::DroppedFile(path) => {
-                self.dropped_files.insert(path.clone());
+                if self.dropped_files.insert(path.clone()) {
+                    self.dropped_files_ordered.push(path.clone());
+                }
             }
             WindowEvent::HoveredFile(path) => {
                 self.hovered_files.insert(path.clone());
             }
             WindowEvent::HoveredFileCancelled => self.hover_cancelled = Some(true),
-            WindowEvent::Ime(ime) => self.ime.push(ime.clone()),
-            &WindowEvent::Occluded(is_occluded) => self.occluded = Some(is_occluded),
-            &WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
-                self.scale_factor = Some(scale_factor)
+            WindowEvent::Ime(ime) => {
+                self.ime.push(ime.clone());
+                self.ime_state.update(ime);
+            }
+            &WindowEvent::Occluded(is_occluded) => {
+                self.occluded = Some(is_occluded);
+                self.currently_occluded = is_occluded;
+            }
+            &WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                ref new_inner_size,
+            } => {
+                self.scale_factor = Some(scale_factor);
+                self.size = Some(**new_inner_size);
+                self.current_scale_factor = Some(scale_factor);
             }
             &WindowEvent::ThemeChanged(theme) => self.theme = Some(theme),
             &WindowEvent::Touch(touch) => {
                 self.touch.push(touch.into());
+                self.touches.update(touch.into());
 
                 #[cfg(feature = "windows_with_device_ids")]
                 self.touch_with_id
@@ -211,15 +530,34 @@ impl WindowCallbackData {
                 ..
             } => {
                 self.cursor_moved = Some(position);
+                self.cursor_inside = true;
 
-                #[cfg(feature = "windows_with_device_ids")]
-                {
-                    *self.cursor_moved_with_id.entry(device_id).or_default() = Some(position);
+                if self.cursor_step_origin.is_none() {
+                    self.cursor_step_origin = Some(self.cursor_position.unwrap_or(position));
+                }
 
+                if let Some(previous) = self.cursor_position {
+                    self.cursor_travel += (position.x - previous.x).hypot(position.y - previous.y);
                 }
+                self.cursor_position = Some(position);
+
+                if self.track_cursor_path {
+                    self.cursor_path_positions.push(position);
+                }
+
+                for (button, delta, started) in self.drags.move_cursor(position) {
+                    if started {
+                        self.drag_started.insert(button);
+                    }
+                    self.drag_delta.insert(button, delta);
+                }
+
+                #[cfg(feature = "windows_with_device_ids")]
+                self.cursor_moved_with_id.insert(device_id, Some(position));
             },
             &WindowEvent::CursorEntered { device_id } => {
                 self.cursor_entered = Some(true);
+                self.cursor_inside = true;
 
                 #[cfg(feature = "windows_with_device_ids")]
                 {
@@ -228,6 +566,8 @@ impl WindowCallbackData {
             }
             &WindowEvent::CursorLeft { device_id } => {
                 self.cursor_entered = Some(false);
+                self.cursor_inside = false;
+                self.cursor_position = None;
 
                 #[cfg(feature = "windows_with_device_ids")]
                 {
@@ -241,6 +581,22 @@ impl WindowCallbackData {
             } => {
                 self.touchpad_magnify.push((delta, phase));
 
+                match phase {
+                    TouchPhase::Started => {
+                        self.magnify_gesture_delta = delta;
+                        self.magnify_gesture_active = true;
+                    }
+                    TouchPhase::Moved => self.magnify_gesture_delta += delta,
+                    TouchPhase::Ended => {
+                        self.magnify_gesture_delta += delta;
+                        self.magnify_gesture_active = false;
+                    }
+                    TouchPhase::Cancelled => {
+                        self.magnify_gesture_delta = 0.0;
+                        self.magnify_gesture_active = false;
+                    }
+                }
+
                 #[cfg(feature = "windows_with_device_ids")]
                 {
                     self.touchpad_magnify_with_id.entry(device_id).or_default().push((delta, phase));
@@ -252,10 +608,12 @@ impl WindowCallbackData {
                 phase,
             } => {
                 self.touchpad_rotate.push((delta, phase));
+                accumulate_rotation(&mut self.rotate_gesture_delta, delta, phase);
 
                 #[cfg(feature = "windows_with_device_ids")]
                 {
                     self.touchpad_rotate_with_id.entry(device_id).or_default().push((delta, phase));
+                    accumulate_rotation(self.rotate_gesture_delta_with_id.entry(device_id).or_default(), delta, phase);
                 }
             },
             &WindowEvent::SmartMagnify { device_id } => {
@@ -263,9 +621,434 @@ impl WindowCallbackData {
 
                 #[cfg(feature = "windows_with_device_ids")]
                 {
-                    *self.smart_magnify_with_id.entry(device_id).or_default() += 1;
+                    *self.smart_magnify_with_id.entry(device_id).or_default().get_or_insert(0) += 1;
                 }
             },
         }
     }
 }
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// The IME composition state of a window.
+///
+/// The preedit text and whether the IME is enabled are kept across steps,
+/// committed strings are cleared every step.
+pub struct ImeState {
+    pub enabled: bool,
+    /// The text currently being composed.
+    pub preedit: String,
+    /// The byte range of the cursor in [ImeState::preedit], or `None` if it should be hidden.
+    pub preedit_cursor: Option<(usize, usize)>,
+    /// The strings committed this step.
+    pub commits: Vec<String>,
+}
+
+impl<D> CallbackCallable<D> for ImeState {
+    type CallbackStruct = ();
+}
+
+impl ImeState {
+    pub fn update(&mut self, ime: &Ime) {
+        match ime {
+            Ime::Enabled => self.enabled = true,
+            Ime::Preedit(preedit, cursor) => {
+                self.preedit.clone_from(preedit);
+                self.preedit_cursor = *cursor;
+            }
+            Ime::Commit(text) => {
+                self.preedit.clear();
+                self.preedit_cursor = None;
+                self.commits.push(text.clone());
+            }
+            Ime::Disabled => {
+                self.enabled = false;
+                self.preedit.clear();
+                self.preedit_cursor = None;
+            }
+        }
+    }
+
+    /// Clears the committed strings
+    pub fn clear(&mut self) {
+        self.commits.clear();
+    }
+}
+
+/// The number of pixels of a [MouseScrollDelta::PixelDelta] that make up a single tick,
+/// see [WindowCallbackData::scroll_ticks].
+pub const PIXELS_PER_SCROLL_TICK: f64 = 50.0;
+
+/// How long after the last move or resize event a window still counts as being moved or resized,
+/// see [WindowCallbackData::being_moved] and [WindowCallbackData::being_resized].
+pub const WINDOW_INTERACTION_QUIET_PERIOD: Duration = Duration::from_millis(100);
+
+fn within_quiet_period(instant: Option<Instant>) -> bool {
+    instant.is_some_and(|instant| instant.elapsed() < WINDOW_INTERACTION_QUIET_PERIOD)
+}
+
+/// A callback for [WindowCallbacks::resized_settled] together with its quiet duration
+/// and the resize instant it last activated for, so it only activates once per resize.
+pub type ResizeSettledCallback<D> = (Duration, CBI<D, PhysicalSize<u32>>, Cell<Option<Instant>>);
+
+#[derive(Clone, Debug, Default)]
+/// Keeps track of the most recent resize of a window, see [WindowCallbacks::resized_settled].
+pub struct ResizeSettle {
+    last_resized: Option<(Instant, PhysicalSize<u32>)>,
+}
+
+impl<D> CallbackCallable<D> for ResizeSettle {
+    type CallbackStruct = Vec<ResizeSettledCallback<D>>;
+
+    fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {
+        let Some((resized_at, size)) = self.last_resized else {
+            return;
+        };
+
+        for (duration, callback, fired_for) in callbacks {
+            if resized_at.elapsed() >= *duration && fired_for.get() != Some(resized_at) {
+                fired_for.set(Some(resized_at));
+                crate::__invoke_callback!(callback, event_helper, size);
+            }
+        }
+    }
+}
+
+impl ResizeSettle {
+    /// Returns the instant and size of the most recent resize
+    pub fn last_resized(&self) -> Option<(Instant, PhysicalSize<u32>)> {
+        self.last_resized
+    }
+}
+
+impl<D> WindowCallbacks<D> {
+    /// Adds a callback that will activate when a single finger swiped in the given direction,
+    /// overwriting any previous callback for the same direction.
+    ///
+    /// See [TouchData::swipe].
+    pub fn swipe(&mut self, direction: SwipeDirection, callback: crate::__callback_arg!(CB<D>)) {
+        self.touches.swipe(direction, callback);
+    }
+
+    /// Adds a callback that will activate when any of the given keys was just pressed or just released,
+    /// depending on `state`, overwriting existing callbacks for the same keys.
+    ///
+    /// Returns a handle for the binding of every key, see [InputCallbacks::just_pressed_any](crate::input::InputCallbacks::just_pressed_any).
+    pub fn keyboard_input_range(
+        &mut self,
+        keys: impl IntoIterator<Item = VirtualKeyCode>,
+        state: ElementState,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> Vec<BindingHandle> {
+        match state {
+            ElementState::Pressed => self.inputs.just_pressed_any(keys, callback),
+            ElementState::Released => self.inputs.just_released_any(keys, callback),
+        }
+    }
+
+    /// Adds a callback that will activate for every just pressed or just released key the predicate returns true for,
+    /// e.g. for reacting to the keys of the current keymap layer.
+    ///
+    /// See [InputCallbacks::keyboard_input_filtered](crate::input::InputCallbacks::keyboard_input_filtered).
+    pub fn keyboard_input_filtered(
+        &mut self,
+        predicate: fn((VirtualKeyCode, ElementState)) -> bool,
+        callback: crate::__callback_arg!(CBI<D, (VirtualKeyCode, ElementState)>),
+    ) {
+        self.inputs.keyboard_input_filtered(predicate, callback);
+    }
+
+    /// Adds a callback that is called with the new size once no resize has been received for `duration`.
+    ///
+    /// Unlike [WindowCallbacks::resized], this is called once per series of resizes,
+    /// such as when the user drags the window border.
+    ///
+    /// The callback is called on the first step that happens after the duration has passed,
+    /// so the event loop has to keep producing steps (e.g. with [ControlFlow::Poll](winit::event_loop::ControlFlow::Poll)
+    /// or [ControlFlow::WaitUntil](winit::event_loop::ControlFlow::WaitUntil)).
+    pub fn resized_settled(
+        &mut self,
+        duration: Duration,
+        callback: crate::__callback_arg!(CBI<D, PhysicalSize<u32>>),
+    ) {
+        self.resize_settle.push((
+            duration,
+            crate::__store_callback!(callback),
+            Cell::new(None),
+        ));
+    }
+}
+
+/// The default distance in physical pixels the cursor has to move while a button is held to start a drag.
+pub const DEFAULT_DRAG_THRESHOLD: f64 = 4.0;
+
+#[derive(Clone, Copy)]
+struct Drag {
+    origin: PhysicalPosition<f64>,
+    dragging: bool,
+}
+
+#[derive(Clone)]
+/// Keeps track of held mouse buttons to detect drags.
+///
+/// A drag starts once the cursor moves further than the threshold from where the button was pressed,
+/// so small movements while clicking are not treated as drags.
+pub struct DragTracker {
+    drags: AHashMap<MouseButton, Drag>,
+    threshold: f64,
+}
+
+impl Default for DragTracker {
+    fn default() -> Self {
+        Self {
+            drags: AHashMap::new(),
+            threshold: DEFAULT_DRAG_THRESHOLD,
+        }
+    }
+}
+
+impl DragTracker {
+    /// Sets the distance in physical pixels the cursor has to move while a button is held to start a drag
+    pub fn set_threshold(&mut self, threshold: f64) {
+        self.threshold = threshold;
+    }
+
+    /// Returns true if the given button is currently dragging
+    pub fn dragging(&self, button: MouseButton) -> bool {
+        self.drags.get(&button).is_some_and(|drag| drag.dragging)
+    }
+
+    fn press(&mut self, button: MouseButton, origin: PhysicalPosition<f64>) {
+        self.drags.insert(
+            button,
+            Drag {
+                origin,
+                dragging: false,
+            },
+        );
+    }
+
+    /// Returns true if the button was dragging
+    fn release(&mut self, button: MouseButton) -> bool {
+        self.drags
+            .remove(&button)
+            .is_some_and(|drag| drag.dragging)
+    }
+
+    /// Returns the movement since the button went down for every dragging button
+    /// and whether that button just started dragging.
+    fn move_cursor(
+        &mut self,
+        position: PhysicalPosition<f64>,
+    ) -> Vec<(MouseButton, PhysicalPosition<f64>, bool)> {
+        let threshold = self.threshold;
+
+        self.drags
+            .iter_mut()
+            .filter_map(|(&button, drag)| {
+                let delta =
+                    PhysicalPosition::new(position.x - drag.origin.x, position.y - drag.origin.y);
+                let started = !drag.dragging && delta.x.hypot(delta.y) >= threshold;
+                drag.dragging |= started;
+
+                drag.dragging.then_some((button, delta, started))
+            })
+            .collect()
+    }
+}
+
+/// Adds the rotation delta of a touchpad rotation event to the summed deltas of its gesture.
+fn accumulate_rotation(total: &mut f32, delta: f32, phase: TouchPhase) {
+    match phase {
+        TouchPhase::Started => *total = delta,
+        TouchPhase::Moved | TouchPhase::Ended => *total += delta,
+        TouchPhase::Cancelled => *total = 0.0,
+    }
+}
+
+#[cfg(feature = "diagnostics")]
+/// Returns the name of the variant of the given event.
+fn window_event_name(event: &WindowEvent) -> &'static str {
+    match event {
+        WindowEvent::Resized(_) => "Resized",
+        WindowEvent::Moved(_) => "Moved",
+        WindowEvent::CloseRequested => "CloseRequested",
+        WindowEvent::Destroyed => "Destroyed",
+        WindowEvent::DroppedFile(_) => "DroppedFile",
+        WindowEvent::HoveredFile(_) => "HoveredFile",
+        WindowEvent::HoveredFileCancelled => "HoveredFileCancelled",
+        WindowEvent::ReceivedCharacter(_) => "ReceivedCharacter",
+        WindowEvent::Focused(_) => "Focused",
+        WindowEvent::KeyboardInput { .. } => "KeyboardInput",
+        WindowEvent::ModifiersChanged(_) => "ModifiersChanged",
+        WindowEvent::Ime(_) => "Ime",
+        WindowEvent::CursorMoved { .. } => "CursorMoved",
+        WindowEvent::CursorEntered { .. } => "CursorEntered",
+        WindowEvent::CursorLeft { .. } => "CursorLeft",
+        WindowEvent::MouseWheel { .. } => "MouseWheel",
+        WindowEvent::MouseInput { .. } => "MouseInput",
+        WindowEvent::TouchpadMagnify { .. } => "TouchpadMagnify",
+        WindowEvent::SmartMagnify { .. } => "SmartMagnify",
+        WindowEvent::TouchpadRotate { .. } => "TouchpadRotate",
+        WindowEvent::TouchpadPressure { .. } => "TouchpadPressure",
+        WindowEvent::AxisMotion { .. } => "AxisMotion",
+        WindowEvent::Touch(_) => "Touch",
+        WindowEvent::ScaleFactorChanged { .. } => "ScaleFactorChanged",
+        WindowEvent::ThemeChanged(_) => "ThemeChanged",
+        WindowEvent::Occluded(_) => "Occluded",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing, Callbacks, EventHelper};
+
+    #[test]
+    fn ime_preedit_then_commit() {
+        let mut data = WindowCallbackData::default();
+
+        data.update(&WindowEvent::Ime(Ime::Enabled));
+        data.update(&WindowEvent::Ime(Ime::Preedit("ni".to_string(), Some((2, 2)))));
+        assert_eq!(data.ime_preedit(), "ni");
+        assert!(data.ime_commits().is_empty());
+
+        data.update(&WindowEvent::Ime(Ime::Commit("你".to_string())));
+        assert_eq!(data.ime_preedit(), "");
+        assert_eq!(data.ime_commits(), ["你"]);
+        assert!(data.ime_state().enabled);
+
+        data.clear();
+        assert!(data.ime_commits().is_empty());
+        assert!(data.ime_state().enabled);
+    }
+
+    #[test]
+    fn keyboard_input_range_activates_for_every_key() {
+        let mut eh = EventHelper::new(0);
+        let mut callbacks = Callbacks::<i32>::empty();
+        let digits = [VirtualKeyCode::Key1, VirtualKeyCode::Key2, VirtualKeyCode::Key3];
+        callbacks
+            .window
+            .keyboard_input_range(digits, ElementState::Pressed, |eh| **eh += 1);
+        callbacks
+            .window
+            .keyboard_input_range(digits, ElementState::Released, |eh| **eh += 10);
+
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::Key1, ElementState::Pressed);
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::Key3, ElementState::Pressed);
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::A, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 2);
+
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::Key3, ElementState::Released);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 12);
+    }
+
+    #[test]
+    fn keyboard_input_filtered_receives_matching_keys() {
+        let mut eh = EventHelper::new(Vec::new());
+        let mut callbacks = Callbacks::<Vec<(VirtualKeyCode, ElementState)>>::empty();
+        callbacks.window.keyboard_input_filtered(
+            |(key, state)| key == VirtualKeyCode::Q || state == ElementState::Released,
+            |eh, input| eh.push(input),
+        );
+
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::Q, ElementState::Pressed);
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::W, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, [(VirtualKeyCode::Q, ElementState::Pressed)]);
+
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::W, ElementState::Released);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(
+            *eh,
+            [
+                (VirtualKeyCode::Q, ElementState::Pressed),
+                (VirtualKeyCode::W, ElementState::Released)
+            ]
+        );
+    }
+
+    #[test]
+    fn cursor_moves_are_coalesced_unless_tracked() {
+        let mut eh = EventHelper::new(Vec::new());
+        let mut callbacks = Callbacks::<Vec<PhysicalPosition<f64>>>::empty();
+        callbacks.window.cursor_moved(|eh, position| eh.push(position));
+
+        let positions = [(1.0, 1.0), (2.0, 3.0), (4.0, 4.0)].map(PhysicalPosition::from);
+        for position in positions {
+            testing::feed_cursor(&mut eh, &callbacks, position);
+        }
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, [positions[2]]);
+        assert!(eh.data.window.cursor_path().is_empty());
+
+        eh.data.window.track_cursor_path = true;
+        for position in positions {
+            testing::feed_cursor(&mut eh, &callbacks, position);
+        }
+        assert_eq!(eh.data.window.cursor_path(), positions);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, [positions[2], positions[2]]);
+    }
+
+    #[test]
+    fn scale_factor_change_updates_size() {
+        let mut eh = EventHelper::new(Vec::new());
+        let mut callbacks = Callbacks::<Vec<f64>>::empty();
+        callbacks
+            .window
+            .scale_factor(|eh, scale_factor| eh.push(scale_factor));
+
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::ScaleFactorChanged {
+                scale_factor: 1.5,
+                new_inner_size: &mut PhysicalSize::new(1200, 900),
+            },
+        );
+        testing::step(&mut eh, &callbacks);
+
+        assert_eq!(eh.data.window.size, Some(PhysicalSize::new(1200, 900)));
+        assert_eq!(*eh, [1.5]);
+    }
+
+    #[test]
+    fn last_click_records_button_and_cursor_position() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+        let position = PhysicalPosition::new(12.0, 34.0);
+
+        // the cursor position is not known yet
+        testing::feed_mouse(&mut eh, &callbacks, MouseButton::Left, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.data.window.last_click(), None);
+        testing::feed_mouse(&mut eh, &callbacks, MouseButton::Left, ElementState::Released);
+
+        testing::feed_cursor(&mut eh, &callbacks, position);
+        testing::feed_mouse(&mut eh, &callbacks, MouseButton::Right, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.data.window.last_click(), Some((MouseButton::Right, position)));
+
+        // the release does not overwrite the click
+        testing::feed_cursor(&mut eh, &callbacks, PhysicalPosition::new(50.0, 60.0));
+        testing::feed_mouse(&mut eh, &callbacks, MouseButton::Right, ElementState::Released);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.data.window.last_click(), Some((MouseButton::Right, position)));
+
+        // a press after the cursor left the window replaces the click
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::CursorLeft {
+                // SAFETY: the dummy id is never passed to a winit function
+                device_id: unsafe { winit::event::DeviceId::dummy() },
+            },
+        );
+        testing::feed_mouse(&mut eh, &callbacks, MouseButton::Left, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.data.window.last_click(), None);
+    }
+}
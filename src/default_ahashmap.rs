@@ -3,72 +3,65 @@
 use std::{
     borrow::Borrow,
     collections::hash_map::{Drain, Entry, Iter, IterMut, Keys, Values, ValuesMut},
-    hash::Hash,
+    hash::{BuildHasher, Hash},
     ops::{Index, IndexMut},
 };
 
-use ahash::AHashMap;
-use winit::{event::DeviceId, window::WindowId};
+use ahash::{AHashMap, RandomState};
 
-use crate::{
-    callbacks::{
-        device::{DeviceCallbackData, DeviceCallbacks},
-        window::{WindowCallbackData, WindowCallbacks},
-    },
-    CallbackCallable, EventHelper,
-};
+use crate::{CallbackCallable, EventHelper};
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone)]
 /// Copied (and slightly altered) from the [defaultmap](https://crates.io/crates/defaultmap) crate!
 /// As such, the [defaultmap::DefaultHashMap](https://docs.rs/defaultmap/0.5.0/defaultmap/struct.DefaultHashMap.html) documentation applies.
 ///
 /// An `AHashMap` that returns a default when keys are accessed that are not present.
-pub struct DefaultAHashMap<K: Eq + Hash, V: Default> {
-    pub(crate) map: AHashMap<K, V>,
+///
+/// The hasher defaults to the randomly seeded [RandomState] of `ahash`, see [DefaultAHashMap::with_hasher].
+pub struct DefaultAHashMap<K: Eq + Hash, V: Default, S = RandomState> {
+    pub(crate) map: AHashMap<K, V, S>,
     default: V,
 }
 
-impl<D> CallbackCallable<D> for DefaultAHashMap<DeviceId, DeviceCallbackData> {
-    type CallbackStruct = DefaultAHashMap<DeviceId, DeviceCallbacks<D>>;
-
-    fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {
-        self.map
-            .iter()
-            .filter_map(|(key, device_callback_data)| {
-                callbacks.map.get(key).zip(Some(device_callback_data))
-            })
-            .for_each(|(device_callbacks, device_callback_data)| {
-                device_callback_data.call_callbacks(event_helper, device_callbacks);
-            });
+impl<K: Eq + Hash, V: Default + PartialEq, S: BuildHasher> PartialEq for DefaultAHashMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map && self.default == other.default
     }
 }
 
-impl<D> CallbackCallable<D> for DefaultAHashMap<WindowId, WindowCallbackData> {
-    type CallbackStruct = DefaultAHashMap<WindowId, WindowCallbacks<D>>;
+impl<K: Eq + Hash, V: Default + Eq, S: BuildHasher> Eq for DefaultAHashMap<K, V, S> {}
+
+/// Calls the callbacks of every key that has both data and callbacks.
+///
+/// The key is usually a [DeviceId](winit::event::DeviceId) or [WindowId](winit::window::WindowId),
+/// but any hashable type works, which allows synthetic ids to be used where winit ids cannot be constructed.
+impl<D, K, V, S> CallbackCallable<D> for DefaultAHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: CallbackCallable<D> + Default,
+    V::CallbackStruct: Default,
+    S: BuildHasher,
+{
+    type CallbackStruct = DefaultAHashMap<K, V::CallbackStruct, S>;
 
     fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {
         self.map
             .iter()
-            .filter_map(|(key, window_callback_data)| {
-                callbacks.map.get(key).zip(Some(window_callback_data))
-            })
-            .for_each(|(window_callbacks, window_callback_data)| {
-                window_callback_data.call_callbacks(event_helper, window_callbacks);
+            .filter_map(|(key, callback_data)| callbacks.map.get(key).zip(Some(callback_data)))
+            .for_each(|(callbacks, callback_data)| {
+                callback_data.call_callbacks(event_helper, callbacks);
             });
     }
 }
 
-impl<K: Eq + Hash, V: Default> Default for DefaultAHashMap<K, V> {
+impl<K: Eq + Hash, V: Default, S: BuildHasher + Default> Default for DefaultAHashMap<K, V, S> {
     fn default() -> Self {
-        Self {
-            map: AHashMap::default(),
-            default: V::default(),
-        }
+        Self::with_hasher(S::default())
     }
 }
 
-impl<K: Eq + Hash, V: Default> From<AHashMap<K, V>> for DefaultAHashMap<K, V> {
-    fn from(map: AHashMap<K, V>) -> Self {
+impl<K: Eq + Hash, V: Default, S> From<AHashMap<K, V, S>> for DefaultAHashMap<K, V, S> {
+    fn from(map: AHashMap<K, V, S>) -> Self {
         Self {
             map,
             default: Default::default(),
@@ -76,7 +69,24 @@ impl<K: Eq + Hash, V: Default> From<AHashMap<K, V>> for DefaultAHashMap<K, V> {
     }
 }
 
-impl<K: Eq + Hash, V: Default> DefaultAHashMap<K, V> {
+impl<K: Eq + Hash, V: Default, S: BuildHasher> DefaultAHashMap<K, V, S> {
+    /// Creates an empty map that uses the given hasher, see [InputData::with_hasher](crate::input::InputData::with_hasher)
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            map: AHashMap::with_hasher(hasher),
+            default: V::default(),
+        }
+    }
+
+    /// Returns the hasher of the map
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    /// Returns the value of the given key.
+    ///
+    /// Keys that are not present all return a reference to the same shared default value,
+    /// which is never inserted. Use [DefaultAHashMap::get_mut] to modify the value of a key.
     pub fn get<Q, QB: Borrow<Q>>(&self, key: QB) -> &V
     where
         K: Borrow<Q>,
@@ -85,12 +95,30 @@ impl<K: Eq + Hash, V: Default> DefaultAHashMap<K, V> {
         self.map.get(key.borrow()).unwrap_or(&self.default)
     }
 
+    /// Returns an owned copy of the value of the given key, see [DefaultAHashMap::get]
+    pub fn get_cloned<Q, QB: Borrow<Q>>(&self, key: QB) -> V
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+        V: Clone,
+    {
+        self.get(key).clone()
+    }
+
+    /// Returns the value of the given key, inserting the default value if it is not present
     pub fn get_mut(&mut self, key: K) -> &mut V {
         self.map.entry(key).or_default()
     }
+
+    /// Returns the value of the given key, inserting the result of `f` if it is not present
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        self.map.entry(key).or_insert_with(f)
+    }
 }
 
-impl<'a, K: Eq + Hash, KB: Borrow<K>, V: Default> Index<KB> for DefaultAHashMap<K, V> {
+impl<K: Eq + Hash, KB: Borrow<K>, V: Default, S: BuildHasher> Index<KB>
+    for DefaultAHashMap<K, V, S>
+{
     type Output = V;
 
     fn index(&self, index: KB) -> &V {
@@ -98,26 +126,27 @@ impl<'a, K: Eq + Hash, KB: Borrow<K>, V: Default> Index<KB> for DefaultAHashMap<
     }
 }
 
-impl<K: Eq + Hash, V: Default> IndexMut<K> for DefaultAHashMap<K, V> {
+impl<K: Eq + Hash, V: Default, S: BuildHasher> IndexMut<K> for DefaultAHashMap<K, V, S> {
     #[inline]
     fn index_mut(&mut self, index: K) -> &mut V {
         self.get_mut(index)
     }
 }
 
-impl<K: Eq + Hash, V: Default> FromIterator<(K, V)> for DefaultAHashMap<K, V> {
+impl<K: Eq + Hash, V: Default, S: BuildHasher + Default> FromIterator<(K, V)>
+    for DefaultAHashMap<K, V, S>
+{
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = (K, V)>,
     {
-        Self {
-            map: AHashMap::from_iter(iter),
-            default: V::default(),
-        }
+        let mut map = Self::default();
+        map.map.extend(iter);
+        map
     }
 }
 
-impl<K: Eq + Hash, V: Default> DefaultAHashMap<K, V> {
+impl<K: Eq + Hash, V: Default, S: BuildHasher> DefaultAHashMap<K, V, S> {
     pub fn capacity(&self) -> usize {
         self.map.capacity()
     }
@@ -130,27 +159,27 @@ impl<K: Eq + Hash, V: Default> DefaultAHashMap<K, V> {
         self.map.shrink_to_fit()
     }
     #[inline]
-    pub fn keys(&self) -> Keys<K, V> {
+    pub fn keys(&self) -> Keys<'_, K, V> {
         self.map.keys()
     }
     #[inline]
-    pub fn values(&self) -> Values<K, V> {
+    pub fn values(&self) -> Values<'_, K, V> {
         self.map.values()
     }
     #[inline]
-    pub fn values_mut(&mut self) -> ValuesMut<K, V> {
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
         self.map.values_mut()
     }
     #[inline]
-    pub fn iter(&self) -> Iter<K, V> {
+    pub fn iter(&self) -> Iter<'_, K, V> {
         self.map.iter()
     }
     #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
         self.map.iter_mut()
     }
     #[inline]
-    pub fn entry(&mut self, key: K) -> Entry<K, V> {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         self.map.entry(key)
     }
     #[inline]
@@ -162,7 +191,7 @@ impl<K: Eq + Hash, V: Default> DefaultAHashMap<K, V> {
         self.map.is_empty()
     }
     #[inline]
-    pub fn drain(&mut self) -> Drain<K, V> {
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
         self.map.drain()
     }
     #[inline]
@@ -1,5 +1,8 @@
 use std::ops::AddAssign;
 
+#[cfg(feature = "boxed_callbacks")]
+use std::cell::RefCell;
+
 #[cfg(feature = "unique_windows")]
 use ahash::AHashMap;
 #[cfg(feature = "unique_windows")]
@@ -13,22 +16,61 @@ use winit::{
 
 pub use winit::event::{AxisId, ButtonId, MouseButton, ScanCode};
 
+#[cfg(feature = "gamepad")]
+pub use gilrs::{Axis as GamepadAxis, Button as GamepadButton};
+
 use crate::EventHelper;
 
+#[cfg(not(feature = "boxed_callbacks"))]
 /// A callback function with no inputs
 pub type CB<D> = fn(&mut EventHelper<D>);
+#[cfg(not(feature = "boxed_callbacks"))]
 /// A callback function with one input
 pub type CBI<D, I> = fn(&mut EventHelper<D>, I);
 
+#[cfg(feature = "boxed_callbacks")]
+/// A boxed callback closure with no inputs
+pub type CB<D> = RefCell<Box<dyn FnMut(&mut EventHelper<D>)>>;
+#[cfg(feature = "boxed_callbacks")]
+/// A boxed callback closure with one input
+pub type CBI<D, I> = RefCell<Box<dyn FnMut(&mut EventHelper<D>, I)>>;
+
+/// Converts a callback into a [CB], e.g. for storing it in the callbacks of a custom data type,
+/// see [create_callbacks](crate::create_callbacks).
+///
+/// With the `boxed_callbacks` feature the closure is boxed, otherwise the function is returned as is.
+pub fn store_callback<D>(callback: crate::__callback_arg!(CB<D>)) -> CB<D> {
+    crate::__store_callback!(callback)
+}
+
+/// Converts a callback with one input into a [CBI], see [store_callback]
+pub fn store_callback_with<D, I>(callback: crate::__callback_arg!(CBI<D, I>)) -> CBI<D, I> {
+    crate::__store_callback!(callback)
+}
+
+/// Calls a callback stored with [store_callback]
+pub fn invoke_callback<D>(callback: &CB<D>, event_helper: &mut EventHelper<D>) {
+    crate::__invoke_callback!(callback, event_helper)
+}
+
+/// Calls a callback stored with [store_callback_with] with the given input
+pub fn invoke_callback_with<D, I>(
+    callback: &CBI<D, I>,
+    event_helper: &mut EventHelper<D>,
+    input: I,
+) {
+    crate::__invoke_callback!(callback, event_helper, input)
+}
+
 bitflags! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
     pub struct QuitWindow: u8 {
         const DESTROYED       = 0b0000_0001;
         const CLOSE_REQUESTED = 0b0000_0010;
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Quit {
     pub loop_destroyed: bool,
     #[cfg(not(feature = "unique_windows"))]
@@ -38,26 +80,47 @@ pub struct Quit {
     pub user_requested: bool,
 }
 
-impl Default for Quit {
-    fn default() -> Self {
-        Self {
-            loop_destroyed: Default::default(),
-            #[cfg(not(feature = "unique_windows"))]
-            window: QuitWindow::empty(),
-            #[cfg(feature = "unique_windows")]
-            windows: Default::default(),
-            user_requested: Default::default(),
-        }
-    }
-}
-
 impl Quit {
     pub fn any(&self) -> bool {
-        self.loop_destroyed || !self.window.is_empty() || self.user_requested
+        #[cfg(not(feature = "unique_windows"))]
+        let window = !self.window.is_empty();
+        #[cfg(feature = "unique_windows")]
+        let window = self.windows.values().any(|quit| !quit.is_empty());
+
+        self.loop_destroyed || window || self.user_requested
+    }
+
+    #[cfg(not(feature = "unique_windows"))]
+    /// Returns true if the window received a close request
+    pub fn window_close_requested(&self) -> bool {
+        self.window.contains(QuitWindow::CLOSE_REQUESTED)
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Returns true if the given window received a close request, or false for unknown windows
+    pub fn window_close_requested(&self, window_id: WindowId) -> bool {
+        self.windows
+            .get(&window_id)
+            .is_some_and(|quit| quit.contains(QuitWindow::CLOSE_REQUESTED))
+    }
+
+    #[cfg(not(feature = "unique_windows"))]
+    /// Returns true if the window was destroyed
+    pub fn window_destroyed(&self) -> bool {
+        self.window.contains(QuitWindow::DESTROYED)
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Returns true if the given window was destroyed, or false for unknown windows
+    pub fn window_destroyed(&self, window_id: WindowId) -> bool {
+        self.windows
+            .get(&window_id)
+            .is_some_and(|quit| quit.contains(QuitWindow::DESTROYED))
     }
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineDelta {
     right: f32,
     down: f32,
@@ -83,6 +146,7 @@ impl TryFrom<MouseScrollDelta> for LineDelta {
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PixelDelta {
     right: f64,
     down: f64,
@@ -119,18 +183,387 @@ impl TryFrom<MouseScrollDelta> for PixelDelta {
 pub type Modifiers = ModifiersState;
 pub type KeyCode = VirtualKeyCode;
 
+/// Parses modifiers separated by `+`, such as `"Ctrl+Shift"`, e.g. for loading keybindings from a config file.
+///
+/// Names are case-insensitive. `Control`, `Option`, `Cmd`, `Command`, `Super`, `Win` and `Meta`
+/// are accepted as aliases, the last five all meaning [Modifiers::LOGO].
+/// An empty string parses as no modifiers. Returns `None` if any name is unknown.
+pub fn parse_modifiers(s: &str) -> Option<Modifiers> {
+    if s.trim().is_empty() {
+        return Some(Modifiers::empty());
+    }
+
+    s.split('+').try_fold(Modifiers::empty(), |modifiers, name| {
+        let modifier = match name.trim().to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => Modifiers::CTRL,
+            "shift" => Modifiers::SHIFT,
+            "alt" | "option" => Modifiers::ALT,
+            "logo" | "cmd" | "command" | "super" | "win" | "meta" => Modifiers::LOGO,
+            _ => return None,
+        };
+        Some(modifiers | modifier)
+    })
+}
+
+/// Returns a human-readable name for the given key, e.g. for showing keybindings in a settings menu.
+///
+/// Keys that produce a character on a US layout are named after that character.
+///
+/// winit does not expose a mapping from scancodes to keys, so scancodes have no names.
+pub fn keycode_name(key: KeyCode) -> &'static str {
+    match key {
+        KeyCode::Key1 => "1",
+        KeyCode::Key2 => "2",
+        KeyCode::Key3 => "3",
+        KeyCode::Key4 => "4",
+        KeyCode::Key5 => "5",
+        KeyCode::Key6 => "6",
+        KeyCode::Key7 => "7",
+        KeyCode::Key8 => "8",
+        KeyCode::Key9 => "9",
+        KeyCode::Key0 => "0",
+        KeyCode::A => "A",
+        KeyCode::B => "B",
+        KeyCode::C => "C",
+        KeyCode::D => "D",
+        KeyCode::E => "E",
+        KeyCode::F => "F",
+        KeyCode::G => "G",
+        KeyCode::H => "H",
+        KeyCode::I => "I",
+        KeyCode::J => "J",
+        KeyCode::K => "K",
+        KeyCode::L => "L",
+        KeyCode::M => "M",
+        KeyCode::N => "N",
+        KeyCode::O => "O",
+        KeyCode::P => "P",
+        KeyCode::Q => "Q",
+        KeyCode::R => "R",
+        KeyCode::S => "S",
+        KeyCode::T => "T",
+        KeyCode::U => "U",
+        KeyCode::V => "V",
+        KeyCode::W => "W",
+        KeyCode::X => "X",
+        KeyCode::Y => "Y",
+        KeyCode::Z => "Z",
+        KeyCode::Escape => "Escape",
+        KeyCode::F1 => "F1",
+        KeyCode::F2 => "F2",
+        KeyCode::F3 => "F3",
+        KeyCode::F4 => "F4",
+        KeyCode::F5 => "F5",
+        KeyCode::F6 => "F6",
+        KeyCode::F7 => "F7",
+        KeyCode::F8 => "F8",
+        KeyCode::F9 => "F9",
+        KeyCode::F10 => "F10",
+        KeyCode::F11 => "F11",
+        KeyCode::F12 => "F12",
+        KeyCode::F13 => "F13",
+        KeyCode::F14 => "F14",
+        KeyCode::F15 => "F15",
+        KeyCode::F16 => "F16",
+        KeyCode::F17 => "F17",
+        KeyCode::F18 => "F18",
+        KeyCode::F19 => "F19",
+        KeyCode::F20 => "F20",
+        KeyCode::F21 => "F21",
+        KeyCode::F22 => "F22",
+        KeyCode::F23 => "F23",
+        KeyCode::F24 => "F24",
+        KeyCode::Snapshot => "Print Screen",
+        KeyCode::Scroll => "Scroll Lock",
+        KeyCode::Pause => "Pause",
+        KeyCode::Insert => "Insert",
+        KeyCode::Home => "Home",
+        KeyCode::Delete => "Delete",
+        KeyCode::End => "End",
+        KeyCode::PageDown => "Page Down",
+        KeyCode::PageUp => "Page Up",
+        KeyCode::Left => "Left",
+        KeyCode::Up => "Up",
+        KeyCode::Right => "Right",
+        KeyCode::Down => "Down",
+        KeyCode::Back => "Backspace",
+        KeyCode::Return => "Enter",
+        KeyCode::Space => "Space",
+        KeyCode::Compose => "Compose",
+        KeyCode::Caret => "^",
+        KeyCode::Numlock => "Num Lock",
+        KeyCode::Numpad0 => "Numpad 0",
+        KeyCode::Numpad1 => "Numpad 1",
+        KeyCode::Numpad2 => "Numpad 2",
+        KeyCode::Numpad3 => "Numpad 3",
+        KeyCode::Numpad4 => "Numpad 4",
+        KeyCode::Numpad5 => "Numpad 5",
+        KeyCode::Numpad6 => "Numpad 6",
+        KeyCode::Numpad7 => "Numpad 7",
+        KeyCode::Numpad8 => "Numpad 8",
+        KeyCode::Numpad9 => "Numpad 9",
+        KeyCode::NumpadAdd => "Numpad +",
+        KeyCode::NumpadDivide => "Numpad /",
+        KeyCode::NumpadDecimal => "Numpad .",
+        KeyCode::NumpadComma => "Numpad ,",
+        KeyCode::NumpadEnter => "Numpad Enter",
+        KeyCode::NumpadEquals => "Numpad =",
+        KeyCode::NumpadMultiply => "Numpad *",
+        KeyCode::NumpadSubtract => "Numpad -",
+        KeyCode::AbntC1 => "Abnt C1",
+        KeyCode::AbntC2 => "Abnt C2",
+        KeyCode::Apostrophe => "'",
+        KeyCode::Apps => "Menu",
+        KeyCode::Asterisk => "*",
+        KeyCode::At => "@",
+        KeyCode::Ax => "AX",
+        KeyCode::Backslash => "\\",
+        KeyCode::Calculator => "Calculator",
+        KeyCode::Capital => "Caps Lock",
+        KeyCode::Colon => ":",
+        KeyCode::Comma => ",",
+        KeyCode::Convert => "Convert",
+        KeyCode::Equals => "=",
+        KeyCode::Grave => "`",
+        KeyCode::Kana => "Kana",
+        KeyCode::Kanji => "Kanji",
+        KeyCode::LAlt => "Left Alt",
+        KeyCode::LBracket => "[",
+        KeyCode::LControl => "Left Ctrl",
+        KeyCode::LShift => "Left Shift",
+        KeyCode::LWin => "Left Super",
+        KeyCode::Mail => "Mail",
+        KeyCode::MediaSelect => "Media Select",
+        KeyCode::MediaStop => "Media Stop",
+        KeyCode::Minus => "-",
+        KeyCode::Mute => "Mute",
+        KeyCode::MyComputer => "My Computer",
+        KeyCode::NavigateForward => "Navigate Forward",
+        KeyCode::NavigateBackward => "Navigate Backward",
+        KeyCode::NextTrack => "Next Track",
+        KeyCode::NoConvert => "No Convert",
+        KeyCode::OEM102 => "OEM 102",
+        KeyCode::Period => ".",
+        KeyCode::PlayPause => "Play/Pause",
+        KeyCode::Plus => "+",
+        KeyCode::Power => "Power",
+        KeyCode::PrevTrack => "Previous Track",
+        KeyCode::RAlt => "Right Alt",
+        KeyCode::RBracket => "]",
+        KeyCode::RControl => "Right Ctrl",
+        KeyCode::RShift => "Right Shift",
+        KeyCode::RWin => "Right Super",
+        KeyCode::Semicolon => ";",
+        KeyCode::Slash => "/",
+        KeyCode::Sleep => "Sleep",
+        KeyCode::Stop => "Stop",
+        KeyCode::Sysrq => "SysRq",
+        KeyCode::Tab => "Tab",
+        KeyCode::Underline => "_",
+        KeyCode::Unlabeled => "Unlabeled",
+        KeyCode::VolumeDown => "Volume Down",
+        KeyCode::VolumeUp => "Volume Up",
+        KeyCode::Wake => "Wake",
+        KeyCode::WebBack => "Web Back",
+        KeyCode::WebFavorites => "Web Favorites",
+        KeyCode::WebForward => "Web Forward",
+        KeyCode::WebHome => "Web Home",
+        KeyCode::WebRefresh => "Web Refresh",
+        KeyCode::WebSearch => "Web Search",
+        KeyCode::WebStop => "Web Stop",
+        KeyCode::Yen => "¥",
+        KeyCode::Copy => "Copy",
+        KeyCode::Paste => "Paste",
+        KeyCode::Cut => "Cut",
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A key identified by its position on the keyboard rather than the symbol it produces.
+///
+/// Unlike [KeyCode], which depends on the keyboard layout on some platforms,
+/// a physical key stays the same between layouts, so a binding for the key
+/// labeled `W` on QWERTY is the key labeled `Z` on AZERTY.
+///
+/// Scancodes differ between platforms, so physical keys are best recorded
+/// from the user pressing the key (e.g. in a rebinding menu) rather than hardcoded.
+///
+/// Converts into [GenericInput::ScanCode], so it can be used anywhere inputs are accepted, including combinations.
+pub struct PhysicalKey(pub ScanCode);
+
+impl From<ScanCode> for PhysicalKey {
+    fn from(value: ScanCode) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Describes which modifiers have to be held for a combination to match.
+///
+/// Converting [Modifiers] into this requires at least those modifiers to be held,
+/// so `Ctrl+S` also matches while shift is held. Use [ModifierMatch::exact] to prevent that.
+pub struct ModifierMatch {
+    /// The modifiers that have to be held.
+    pub required: Modifiers,
+    /// The modifiers that must not be held.
+    pub forbidden: Modifiers,
+}
+
+impl ModifierMatch {
+    /// Matches when all `required` modifiers and none of the `forbidden` modifiers are held
+    pub fn new(required: Modifiers, forbidden: Modifiers) -> Self {
+        Self {
+            required,
+            forbidden,
+        }
+    }
+
+    /// Matches when at least the given modifiers are held
+    pub fn at_least(modifiers: Modifiers) -> Self {
+        Self {
+            required: modifiers,
+            forbidden: Modifiers::empty(),
+        }
+    }
+
+    /// Matches when exactly the given modifiers are held
+    pub fn exact(modifiers: Modifiers) -> Self {
+        Self {
+            required: modifiers,
+            forbidden: Modifiers::all() - modifiers,
+        }
+    }
+
+    pub fn matches(&self, modifiers: Modifiers) -> bool {
+        modifiers.contains(self.required) && !modifiers.intersects(self.forbidden)
+    }
+}
+
+impl From<Modifiers> for ModifierMatch {
+    fn from(value: Modifiers) -> Self {
+        Self::at_least(value)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CursorState {
     Entered,
     Left,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// The direction of a touch swipe.
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeDirection {
+    /// Returns the direction along the dominant axis of the given movement in physical pixels.
+    pub fn from_delta(dx: f64, dy: f64) -> Self {
+        if dx.abs() >= dy.abs() {
+            if dx >= 0.0 {
+                Self::Right
+            } else {
+                Self::Left
+            }
+        } else if dy >= 0.0 {
+            Self::Down
+        } else {
+            Self::Up
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A generic input type combining inputs that can be pressed.
 pub enum GenericInput {
     MouseButton(MouseButton),
     KeyCode(KeyCode),
     ScanCode(ScanCode),
+    #[cfg(feature = "gamepad")]
+    GamepadButton(GamepadButton),
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The kind of a [GenericInput], e.g. for grouping inputs in a rebinding menu.
+pub enum InputCategory {
+    Mouse,
+    Keyboard,
+    ScanCode,
+    #[cfg(feature = "gamepad")]
+    Gamepad,
+}
+
+impl GenericInput {
+    /// Returns the kind of the input
+    pub fn category(&self) -> InputCategory {
+        match self {
+            Self::MouseButton(_) => InputCategory::Mouse,
+            Self::KeyCode(_) => InputCategory::Keyboard,
+            Self::ScanCode(_) => InputCategory::ScanCode,
+            #[cfg(feature = "gamepad")]
+            Self::GamepadButton(_) => InputCategory::Gamepad,
+        }
+    }
+
+    /// Returns `true` if the input is a [GenericInput::KeyCode].
+    ///
+    /// Scancodes are not included, see [GenericInput::is_scancode].
+    pub fn is_keyboard(&self) -> bool {
+        matches!(self, Self::KeyCode(_))
+    }
+
+    /// Returns `true` if the input is a [GenericInput::MouseButton]
+    pub fn is_mouse(&self) -> bool {
+        matches!(self, Self::MouseButton(_))
+    }
+
+    /// Returns `true` if the input is a [GenericInput::ScanCode]
+    pub fn is_scancode(&self) -> bool {
+        matches!(self, Self::ScanCode(_))
+    }
+
+    #[cfg(feature = "gamepad")]
+    /// Returns `true` if the input is a [GenericInput::GamepadButton]
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Self::GamepadButton(_))
+    }
+
+    /// Returns a key that orders inputs by kind first and by value second.
+    ///
+    /// Needed because [MouseButton] does not implement [Ord].
+    fn order_key(&self) -> (u8, u32) {
+        match *self {
+            Self::MouseButton(MouseButton::Left) => (0, 0),
+            Self::MouseButton(MouseButton::Right) => (0, 1),
+            Self::MouseButton(MouseButton::Middle) => (0, 2),
+            Self::MouseButton(MouseButton::Other(button)) => (0, 3 + button as u32),
+            Self::KeyCode(key) => (1, key as u32),
+            Self::ScanCode(scancode) => (2, scancode),
+            #[cfg(feature = "gamepad")]
+            Self::GamepadButton(button) => (3, button as u32),
+        }
+    }
+}
+
+impl PartialOrd for GenericInput {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GenericInput {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order_key().cmp(&other.order_key())
+    }
 }
 
 impl From<MouseButton> for GenericInput {
@@ -151,6 +584,19 @@ impl From<ScanCode> for GenericInput {
     }
 }
 
+impl From<PhysicalKey> for GenericInput {
+    fn from(value: PhysicalKey) -> Self {
+        Self::ScanCode(value.0)
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<GamepadButton> for GenericInput {
+    fn from(value: GamepadButton) -> Self {
+        Self::GamepadButton(value)
+    }
+}
+
 impl TryFrom<GenericInput> for KeyCode {
     type Error = ();
 
@@ -184,6 +630,61 @@ impl TryFrom<GenericInput> for ScanCode {
     }
 }
 
+impl TryFrom<GenericInput> for PhysicalKey {
+    type Error = ();
+
+    fn try_from(value: GenericInput) -> Result<Self, Self::Error> {
+        ScanCode::try_from(value).map(Self)
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl TryFrom<GenericInput> for GamepadButton {
+    type Error = ();
+
+    fn try_from(value: GenericInput) -> Result<Self, Self::Error> {
+        match value {
+            GenericInput::GamepadButton(value) => Ok(value),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An input-modifier combination, as registered through the `*_combination` functions of [InputCallbacks](crate::input::InputCallbacks).
+///
+/// With the `serde` feature, this can be used to store rebindable controls.
+/// Register a loaded keybinding using the `*_combination` functions on [InputCallbacks](crate::input::InputCallbacks).
+pub struct Keybinding {
+    pub inputs: Vec<GenericInput>,
+    pub modifiers: Modifiers,
+}
+
+impl Keybinding {
+    pub fn new<I: Into<GenericInput>>(
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> Self {
+        Self {
+            inputs: inputs.into_iter().map(|input| input.into()).collect(),
+            modifiers,
+        }
+    }
+}
+
+impl From<(Vec<GenericInput>, Modifiers)> for Keybinding {
+    fn from((inputs, modifiers): (Vec<GenericInput>, Modifiers)) -> Self {
+        Self { inputs, modifiers }
+    }
+}
+
+impl From<Keybinding> for (Vec<GenericInput>, Modifiers) {
+    fn from(value: Keybinding) -> Self {
+        (value.inputs, value.modifiers)
+    }
+}
+
 impl IntoIterator for GenericInput {
     type Item = Self;
 
@@ -195,6 +696,7 @@ impl IntoIterator for GenericInput {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Adapted from [winit::event::Touch]
 pub struct IdLessTouch {
     pub phase: TouchPhase,
@@ -205,11 +707,47 @@ pub struct IdLessTouch {
     /// ## Platform-specific
     ///
     /// - Only available on **iOS** 9.0+ and **Windows** 8+.
+    #[cfg_attr(feature = "serde", serde(with = "force_serde"))]
     pub force: Option<Force>,
     /// Unique identifier of a finger.
     pub id: u64,
 }
 
+#[cfg(feature = "serde")]
+/// (De)serializes [Force], as winit does not implement `serde` for it.
+mod force_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use winit::event::Force;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "Force")]
+    #[allow(dead_code)]
+    enum ForceDef {
+        Calibrated {
+            force: f64,
+            max_possible_force: f64,
+            altitude_angle: Option<f64>,
+        },
+        Normalized(f64),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct ForceWrapper(#[serde(with = "ForceDef")] Force);
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Force>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.map(ForceWrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Force>, D::Error> {
+        Ok(Option::<ForceWrapper>::deserialize(deserializer)?.map(|ForceWrapper(force)| force))
+    }
+}
+
 impl From<Touch> for IdLessTouch {
     fn from(
         Touch {
@@ -229,9 +767,60 @@ impl From<Touch> for IdLessTouch {
     }
 }
 
+impl IdLessTouch {
+    /// Returns the pressure of the touch between 0.0 and 1.0, or `None` if the platform does not report it
+    /// or reports a calibrated force without a positive maximum.
+    ///
+    /// Unlike [Force::normalized], the angle of a stylus is not taken into account.
+    pub fn normalized_force(&self) -> Option<f64> {
+        match self.force? {
+            Force::Calibrated {
+                force,
+                max_possible_force,
+                ..
+            } if max_possible_force > 0.0 => Some((force / max_possible_force).clamp(0.0, 1.0)),
+            Force::Calibrated { .. } => None,
+            Force::Normalized(force) => Some(force.clamp(0.0, 1.0)),
+        }
+    }
+}
+
 pub trait CallbackCallable<D> {
     type CallbackStruct;
 
     #[allow(unused_variables)]
     fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch_with_force(force: Force) -> IdLessTouch {
+        IdLessTouch {
+            phase: TouchPhase::Started,
+            location: PhysicalPosition::new(0.0, 0.0),
+            force: Some(force),
+            id: 0,
+        }
+    }
+
+    #[test]
+    fn normalized_force_handles_both_variants() {
+        let calibrated = |force, max_possible_force| {
+            touch_with_force(Force::Calibrated {
+                force,
+                max_possible_force,
+                altitude_angle: None,
+            })
+        };
+
+        assert_eq!(calibrated(1.0, 4.0).normalized_force(), Some(0.25));
+        assert_eq!(calibrated(1.0, 0.0).normalized_force(), None);
+        assert_eq!(calibrated(1.0, -1.0).normalized_force(), None);
+        assert_eq!(
+            touch_with_force(Force::Normalized(1.5)).normalized_force(),
+            Some(1.0)
+        );
+    }
+}
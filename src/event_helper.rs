@@ -1,16 +1,43 @@
 use std::{
+    collections::VecDeque,
     ops::{Deref, DerefMut},
+    sync::Arc,
     time::{Duration, Instant},
 };
 
-use winit::event::Event;
+use ahash::AHashMap;
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{Event, StartCause, VirtualKeyCode, WindowEvent},
+    event_loop::ControlFlow,
+    window::{Window, WindowId},
+};
 
 use crate::{
-    callbacks::all::{CallbackData, Callbacks},
-    definitions::CB,
-    Quit, QuitWindow,
+    callbacks::{
+        all::{CallbackData, Callbacks},
+        window::WindowCallbackData,
+    },
+    definitions::{GenericInput, Modifiers},
+    input::{InputChanges, InputData},
+    recording::{EventRecorder, RecordedEvent},
+    Quit,
 };
 
+#[cfg(not(feature = "unique_windows"))]
+use crate::QuitWindow;
+
+/// The number of step intervals [EventHelper::average_steps_per_second] averages over.
+pub const STEP_INTERVAL_WINDOW: usize = 60;
+
+/// The maximum number of times [EventHelper::fixed_update] calls its callback in a single step.
+///
+/// Any time left beyond that is discarded, so slow steps do not cause ever more catch-up work.
+pub const MAX_FIXED_UPDATES_PER_STEP: u32 = 8;
+
+/// A function queued by [EventHelper::call_after_delay] together with its deadline.
+type DelayedCallback<D> = (Instant, fn(&mut EventHelper<D>));
+
 /// A struct holding all the callback functions and user function data.
 /// Also has some helper functions.
 ///
@@ -21,7 +48,7 @@ pub struct EventHelper<D> {
     /// The data for the event callbacks.
     pub data: CallbackData,
     clear_callback_data: bool,
-    call_after: Vec<CB<D>>,
+    call_after: Vec<fn(&mut EventHelper<D>)>,
     /// Stores the instants the last two [EventHelper::update]s were called.
     ///
     /// Required for [EventHelper::time_since_previous_step]
@@ -29,6 +56,33 @@ pub struct EventHelper<D> {
     time_since_start: Instant,
     update_count: usize,
     quit: Quit,
+    /// The multipliers applied to raw mouse motion along the x and y axes.
+    mouse_sensitivity: (f64, f64),
+    mouse_inverted: (bool, bool),
+    /// The path length in physical pixels the cursor travelled since the start.
+    total_cursor_travel: f64,
+    /// The intervals between the last [STEP_INTERVAL_WINDOW] steps.
+    ///
+    /// Required for [EventHelper::average_steps_per_second]
+    step_intervals: VecDeque<Duration>,
+    /// The time not yet consumed by [EventHelper::fixed_update].
+    fixed_update_accumulator: Duration,
+    /// The step the time of which was last added to the accumulator, so multiple [EventHelper::fixed_update] calls in the same step do not count it twice.
+    fixed_update_step: usize,
+    /// Set by [EventHelper::consume_input] to stop lower priority input callbacks from activating.
+    pub(crate) input_consumed: bool,
+    /// Callbacks queued by [EventHelper::call_after_delay], sorted by deadline.
+    call_after_delay: VecDeque<DelayedCallback<D>>,
+    /// The windows registered through [EventHelper::register_window].
+    windows: AHashMap<WindowId, Arc<Window>>,
+    /// Records the events passed to [EventHelper::update] while set.
+    recorder: Option<EventRecorder>,
+    /// The cause of the most recent [Event::NewEvents], kept across steps.
+    last_start_cause: Option<StartCause>,
+    /// Whether the callback data is only cleared through [EventHelper::clear_now], see [EventHelper::set_auto_clear].
+    manual_clear: bool,
+    /// The copy of [EventHelper::data] the callbacks are called with, kept so its allocations are reused every step.
+    callback_scratch: Option<CallbackData>,
 }
 
 impl<D: Clone> Clone for EventHelper<D> {
@@ -36,12 +90,25 @@ impl<D: Clone> Clone for EventHelper<D> {
         Self {
             user_data: self.user_data.clone(),
             data: self.data.clone(),
-            clear_callback_data: self.clear_callback_data.clone(),
+            clear_callback_data: self.clear_callback_data,
             call_after: self.call_after.clone(),
-            last_steps: self.last_steps.clone(),
-            time_since_start: self.time_since_start.clone(),
-            update_count: self.update_count.clone(),
+            last_steps: self.last_steps,
+            time_since_start: self.time_since_start,
+            update_count: self.update_count,
             quit: self.quit.clone(),
+            mouse_sensitivity: self.mouse_sensitivity,
+            mouse_inverted: self.mouse_inverted,
+            total_cursor_travel: self.total_cursor_travel,
+            step_intervals: self.step_intervals.clone(),
+            fixed_update_accumulator: self.fixed_update_accumulator,
+            fixed_update_step: self.fixed_update_step,
+            input_consumed: self.input_consumed,
+            call_after_delay: self.call_after_delay.clone(),
+            windows: self.windows.clone(),
+            recorder: self.recorder.clone(),
+            last_start_cause: self.last_start_cause,
+            manual_clear: self.manual_clear,
+            callback_scratch: None,
         }
     }
 }
@@ -57,6 +124,19 @@ impl<D: Default> Default for EventHelper<D> {
             time_since_start: Instant::now(),
             update_count: 0,
             quit: Default::default(),
+            mouse_sensitivity: (1.0, 1.0),
+            mouse_inverted: (false, false),
+            total_cursor_travel: 0.0,
+            step_intervals: VecDeque::with_capacity(STEP_INTERVAL_WINDOW),
+            fixed_update_accumulator: Duration::ZERO,
+            fixed_update_step: 0,
+            input_consumed: false,
+            call_after_delay: VecDeque::new(),
+            windows: AHashMap::new(),
+            recorder: None,
+            last_start_cause: None,
+            manual_clear: false,
+            callback_scratch: None,
         }
     }
 }
@@ -87,33 +167,98 @@ impl<D> EventHelper<D> {
             time_since_start: Instant::now(),
             update_count: 0,
             quit: Default::default(),
+            mouse_sensitivity: (1.0, 1.0),
+            mouse_inverted: (false, false),
+            total_cursor_travel: 0.0,
+            step_intervals: VecDeque::with_capacity(STEP_INTERVAL_WINDOW),
+            fixed_update_accumulator: Duration::ZERO,
+            fixed_update_step: 0,
+            input_consumed: false,
+            call_after_delay: VecDeque::new(),
+            windows: AHashMap::new(),
+            recorder: None,
+            last_start_cause: None,
+            manual_clear: false,
+            callback_scratch: None,
         }
     }
 
     #[inline]
     /// Pass all [Event]s to this function.
     /// When it returns true, a `step` has passed and application logic can be run.
+    ///
+    /// The callbacks are called with a copy of the data of the finished step,
+    /// so they can read and change [EventHelper::data] like any other code.
     pub fn update<'a, E: PartialEq>(
         &mut self,
-        callbacks: &Callbacks<D>,
+        callbacks: &Callbacks<D, E>,
         event: &Event<'a, E>,
     ) -> bool {
-        self.call_after.clone().iter().for_each(|func| func(self));
-        self.call_after.clear();
+        std::mem::take(&mut self.call_after)
+            .into_iter()
+            .for_each(|func| func(self));
 
-        if self.clear_callback_data {
-            self.clear_callback_data = false;
-            self.data.clear();
+        let now = Instant::now();
+        while let Some(&(deadline, func)) = self.call_after_delay.front() {
+            if deadline > now {
+                break;
+            }
+            self.call_after_delay.pop_front();
+            func(self);
+        }
+
+        self.clear_finished_step();
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(event);
+        }
+
+        if let Some(raw_event) = &callbacks.raw_event {
+            crate::__invoke_callback!(raw_event, self, event);
+        }
+
+        if let &Event::NewEvents(start_cause) = event {
+            self.last_start_cause = Some(start_cause);
         }
 
         if *event == Event::MainEventsCleared {
             self.update_count += 1;
+            self.data.general.first_update = self.update_count == 1;
             self.last_steps = [self.last_steps[1], Instant::now()];
-            self.data.clone().call_callbacks(self, callbacks);
-            self.clear_callback_data = true;
+
+            // the first step has no previous step to measure the interval from
+            if self.update_count > 1 {
+                if self.step_intervals.len() == STEP_INTERVAL_WINDOW {
+                    self.step_intervals.pop_front();
+                }
+                self.step_intervals
+                    .push_back(self.last_steps[1] - self.last_steps[0]);
+            }
+
+            self.total_cursor_travel += self.cursor_travel_this_step();
+            // copied into a reused buffer, so the callbacks can borrow `self` mutably
+            let data = match self.callback_scratch.take() {
+                Some(mut scratch) => {
+                    scratch.clone_from(&self.data);
+                    scratch
+                }
+                None => self.data.clone(),
+            };
+            data.call_callbacks(self, callbacks);
+            self.callback_scratch = Some(data);
+
+            self.clear_callback_data = !self.manual_clear;
             return true;
         }
 
+        if let Event::WindowEvent {
+            window_id,
+            event: WindowEvent::Destroyed,
+        } = event
+        {
+            self.windows.remove(window_id);
+        }
+
         self.data.update(event);
 
         self.quit.loop_destroyed = self.data.general.loop_destroyed;
@@ -123,32 +268,302 @@ impl<D> EventHelper<D> {
         }
         #[cfg(feature = "unique_windows")]
         {
-            self.quit.windows = self.data.window.iter().filter_map(|(id, data)| (id, data.quit.clone())).collect()
+            let windows = &self.data.windows;
+            self.quit
+                .windows
+                .retain(|window_id, _| windows.get(window_id).quit.is_some());
+
+            if let Event::WindowEvent { window_id, .. } = event {
+                if let Some(quit) = windows.get(window_id).quit.clone() {
+                    self.quit.windows.insert(*window_id, quit);
+                }
+            }
         }
 
         false
     }
 
+    #[inline]
+    /// Same as [EventHelper::update], but also sets `control_flow` to [ControlFlow::Exit]
+    /// when any [Quit] flag is set.
+    ///
+    /// `control_flow` is left untouched otherwise.
+    pub fn update_with_control_flow<'a, E: PartialEq>(
+        &mut self,
+        callbacks: &Callbacks<D, E>,
+        event: &Event<'a, E>,
+        control_flow: &mut ControlFlow,
+    ) -> bool {
+        let step = self.update(callbacks, event);
+
+        if self.quit.any() {
+            *control_flow = ControlFlow::Exit;
+        }
+
+        step
+    }
+
+    #[cfg(feature = "gamepad")]
+    /// Drains the pending `gilrs` events and updates [CallbackData::gamepad] with them,
+    /// so gamepad buttons can be used like any other [GenericInput](crate::definitions::GenericInput).
+    ///
+    /// Call this before [EventHelper::update] so the events are part of the next step.
+    pub fn poll_gamepads(&mut self, gilrs: &mut gilrs::Gilrs) {
+        use gilrs::EventType;
+
+        self.clear_finished_step();
+
+        while let Some(gilrs::Event { event, .. }) = gilrs.next_event() {
+            match event {
+                EventType::ButtonPressed(button, _) => self.data.gamepad.press(button),
+                EventType::ButtonReleased(button, _) => self.data.gamepad.release(button),
+                EventType::AxisChanged(axis, value, _) => self.data.gamepad.update_axis(axis, value),
+                _ => (),
+            }
+        }
+    }
+
+    /// Clears the callback data if a step finished since the last call.
+    fn clear_finished_step(&mut self) {
+        if self.clear_callback_data {
+            self.clear_callback_data = false;
+            self.data.clear();
+        }
+    }
+
+    /// Sets whether the callback data is cleared automatically before the first event after a step.
+    ///
+    /// On by default. When turned off, the data accumulates across steps
+    /// until [EventHelper::clear_now] is called, which has to be done every step.
+    pub fn set_auto_clear(&mut self, auto_clear: bool) {
+        self.manual_clear = !auto_clear;
+    }
+
+    pub fn auto_clear(&self) -> bool {
+        !self.manual_clear
+    }
+
+    /// Clears the callback data immediately, see [EventHelper::set_auto_clear]
+    pub fn clear_now(&mut self) {
+        self.clear_callback_data = false;
+        self.data.clear();
+    }
+
+    /// Stores the given window so callbacks can access it through [EventHelper::window],
+    /// returning the stored handle.
+    ///
+    /// winit does not pass windows to the event loop, so windows have to be registered after creation.
+    /// Windows are removed automatically when they are destroyed.
+    pub fn register_window(&mut self, window: impl Into<Arc<Window>>) -> Arc<Window> {
+        let window = window.into();
+        self.windows.insert(window.id(), window.clone());
+        window
+    }
+
+    /// Removes the given window, returning it if it was registered
+    pub fn unregister_window(&mut self, window_id: WindowId) -> Option<Arc<Window>> {
+        self.windows.remove(&window_id)
+    }
+
+    /// Returns the registered window with the given id, see [EventHelper::register_window]
+    pub fn window(&self, window_id: WindowId) -> Option<&Arc<Window>> {
+        self.windows.get(&window_id)
+    }
+
+    /// Requests a redraw of the registered window with the given id, see [Window::request_redraw]
+    ///
+    /// Returns `false` if no window with the given id is registered.
+    pub fn request_redraw(&self, window_id: WindowId) -> bool {
+        self.windows
+            .get(&window_id)
+            .map(|window| window.request_redraw())
+            .is_some()
+    }
+
+    /// Requests a redraw of every registered window, see [Window::request_redraw]
+    ///
+    /// Does nothing if no windows are registered.
+    pub fn request_redraw_all(&self) {
+        self.windows.values().for_each(|window| window.request_redraw());
+    }
+
     /// Returns the number of steps that have passed so far
     pub fn update_count(&self) -> usize {
         self.update_count
     }
 
     /// Adds the given function to the queue to be called before the next event is handled
-    pub fn call_after(&mut self, callback: CB<D>) {
+    ///
+    /// This always takes a function pointer, even with the `boxed_callbacks` feature enabled.
+    ///
+    /// Functions added while the queue is being called are called before the event after that.
+    pub fn call_after(&mut self, callback: fn(&mut EventHelper<D>)) {
         self.call_after.push(callback);
     }
 
-    /// Returns the time since the [EventHelper] struct was created
+    /// Adds the given function to the queue to be called before the first event
+    /// that is handled after `delay` has passed
+    ///
+    /// Functions with the same deadline are called in the order they were added.
+    /// This always takes a function pointer, even with the `boxed_callbacks` feature enabled.
+    pub fn call_after_delay(&mut self, delay: Duration, callback: fn(&mut EventHelper<D>)) {
+        let deadline = Instant::now() + delay;
+        let index = self
+            .call_after_delay
+            .partition_point(|&(other, _)| other <= deadline);
+        self.call_after_delay.insert(index, (deadline, callback));
+    }
+
+    /// Returns the time between the creation of the [EventHelper] struct and the start of the current step,
+    /// see [EventHelper::step_time].
+    ///
+    /// The value is the same throughout a step. Use [EventHelper::time_since_start_live] for the current time.
     pub fn time_since_start(&self) -> Duration {
+        self.step_time().duration_since(self.time_since_start)
+    }
+
+    /// Returns the time since the [EventHelper] struct was created
+    pub fn time_since_start_live(&self) -> Duration {
         self.time_since_start.elapsed()
     }
 
+    /// Returns the instant the current step started at, i.e. the last time [EventHelper::update] returned `true`,
+    /// or the creation of the [EventHelper] struct if no step has passed yet.
+    ///
+    /// The value is the same throughout a step, so all logic in a step can use the same timestamp.
+    pub fn step_time(&self) -> Instant {
+        self.last_steps[1]
+    }
+
     /// Returns the time since the previous time [EventHelper::update] returned `true`
     pub fn time_since_previous_step(&self) -> Duration {
         self.last_steps[0].elapsed()
     }
 
+    /// Returns the step rate based on the interval between the last two steps,
+    /// or 0.0 if fewer than two steps have passed
+    pub fn steps_per_second(&self) -> f64 {
+        if self.update_count < 2 {
+            return 0.0;
+        }
+        per_second(self.last_steps[1] - self.last_steps[0], 1)
+    }
+
+    /// Returns the average step rate over the last [STEP_INTERVAL_WINDOW] steps,
+    /// or 0.0 if no steps have passed since the last reset
+    pub fn average_steps_per_second(&self) -> f64 {
+        per_second(
+            self.step_intervals.iter().sum(),
+            self.step_intervals.len(),
+        )
+    }
+
+    /// Clears the intervals used by [EventHelper::average_steps_per_second]
+    pub fn reset_average_steps_per_second(&mut self) {
+        self.step_intervals.clear();
+    }
+
+    /// Calls `callback` once for every `dt` that passed, carrying the remainder over to the next step.
+    ///
+    /// Call this once every step after [EventHelper::update] returned true.
+    /// The callback is called at most [MAX_FIXED_UPDATES_PER_STEP] times per step.
+    ///
+    /// Returns the number of times `callback` was called.
+    pub fn fixed_update(&mut self, dt: Duration, mut callback: impl FnMut(&mut EventHelper<D>)) -> u32 {
+        if dt.is_zero() {
+            return 0;
+        }
+
+        if self.fixed_update_step != self.update_count {
+            self.fixed_update_step = self.update_count;
+            self.fixed_update_accumulator += self.last_steps[1] - self.last_steps[0];
+        }
+
+        let mut count = 0;
+
+        while self.fixed_update_accumulator >= dt {
+            if count == MAX_FIXED_UPDATES_PER_STEP {
+                self.fixed_update_accumulator = Duration::ZERO;
+                break;
+            }
+
+            self.fixed_update_accumulator -= dt;
+            callback(self);
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Stops the remaining input callbacks of the current [InputData] from activating this step.
+    ///
+    /// Combination callbacks activate first, in order of descending priority,
+    /// followed by the sequence, double click, held, repeat, filtered and any-of callbacks.
+    /// Call this from a `*_combination_with_priority` callback to suppress lower priority callbacks.
+    /// The callbacks of other [InputData], e.g. the device inputs, are not affected.
+    pub fn consume_input(&mut self) {
+        self.input_consumed = true;
+    }
+
+    /// Calls the given function with the user data and returns its result
+    pub fn with_data<R>(&mut self, f: impl FnOnce(&mut D) -> R) -> R {
+        f(&mut self.user_data)
+    }
+
+    /// Calls the given function with both the callbacks and the user data,
+    /// e.g. for registering callbacks based on a configuration stored in the user data.
+    ///
+    /// [Callbacks] are not stored in the [EventHelper], so they have to be passed in.
+    pub fn configure<E: 'static>(
+        &mut self,
+        callbacks: &mut Callbacks<D, E>,
+        f: impl FnOnce(&mut Callbacks<D, E>, &mut D),
+    ) {
+        f(callbacks, &mut self.user_data)
+    }
+
+    /// Starts recording every event passed to [EventHelper::update], replacing the current recording
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(EventRecorder::new());
+    }
+
+    /// Stops recording and returns the recorded events, if recording
+    pub fn stop_recording(&mut self) -> Option<EventRecorder> {
+        self.recorder.take()
+    }
+
+    /// Passes the given events to [EventHelper::update] in order
+    ///
+    /// The events are converted to the user event type `E` of the callbacks,
+    /// which always succeeds as user events are not recorded.
+    pub fn replay<E: PartialEq>(&mut self, callbacks: &Callbacks<D, E>, events: &[RecordedEvent]) {
+        for event in events {
+            match *event {
+                RecordedEvent::Event(ref event) => {
+                    if let Ok(event) = event.clone().map_nonuser_event() {
+                        self.update(callbacks, &event);
+                    }
+                }
+                RecordedEvent::ScaleFactorChanged {
+                    window_id,
+                    scale_factor,
+                    mut new_inner_size,
+                } => {
+                    self.update(
+                        callbacks,
+                        &Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::ScaleFactorChanged {
+                                scale_factor,
+                                new_inner_size: &mut new_inner_size,
+                            },
+                        },
+                    );
+                }
+            }
+        }
+    }
+
     /// Sets the `self.quit.user_requested` to `true`
     pub fn request_quit(&mut self) {
         self.quit.user_requested = true;
@@ -158,4 +573,510 @@ impl<D> EventHelper<D> {
     pub fn quit(&self) -> Quit {
         self.quit.clone()
     }
+
+    #[cfg(not(feature = "unique_windows"))]
+    /// Returns true if the window received a close request, see [Quit::window_close_requested]
+    pub fn window_close_requested(&self) -> bool {
+        self.quit.window_close_requested()
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Returns true if the given window received a close request, see [Quit::window_close_requested]
+    pub fn window_close_requested(&self, window_id: WindowId) -> bool {
+        self.quit.window_close_requested(window_id)
+    }
+
+    #[cfg(not(feature = "unique_windows"))]
+    /// Returns true if the window was destroyed, see [Quit::window_destroyed]
+    pub fn window_destroyed(&self) -> bool {
+        self.quit.window_destroyed()
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Returns true if the given window was destroyed, see [Quit::window_destroyed]
+    pub fn window_destroyed(&self, window_id: WindowId) -> bool {
+        self.quit.window_destroyed(window_id)
+    }
+
+    /// Sets the multiplier applied to raw mouse motion along both axes
+    pub fn set_mouse_sensitivity(&mut self, sensitivity: f64) {
+        self.mouse_sensitivity = (sensitivity, sensitivity);
+    }
+
+    /// Sets the multipliers applied to raw mouse motion along the x and y axes separately
+    pub fn set_mouse_sensitivity_xy(&mut self, x: f64, y: f64) {
+        self.mouse_sensitivity = (x, y);
+    }
+
+    /// Sets whether raw mouse motion is inverted along the x and y axes
+    pub fn set_mouse_inverted(&mut self, x: bool, y: bool) {
+        self.mouse_inverted = (x, y);
+    }
+
+    /// Returns the raw mouse motion this step with sensitivity and inversion applied
+    pub fn mouse_delta(&self) -> Option<(f64, f64)> {
+        let (sensitivity_x, sensitivity_y) = self.mouse_sensitivity;
+        let sign = |inverted: bool| if inverted { -1.0 } else { 1.0 };
+
+        self.raw_mouse_delta().map(|(x, y)| {
+            (
+                x * sensitivity_x * sign(self.mouse_inverted.0),
+                y * sensitivity_y * sign(self.mouse_inverted.1),
+            )
+        })
+    }
+
+    /// Returns the raw mouse motion this step, summed over all devices
+    pub fn raw_mouse_delta(&self) -> Option<(f64, f64)> {
+        #[cfg(not(feature = "unique_devices"))]
+        {
+            self.data.device.mouse_motion
+        }
+        #[cfg(feature = "unique_devices")]
+        {
+            self.data
+                .devices
+                .values()
+                .filter_map(|device| device.mouse_motion)
+                .reduce(|(total_x, total_y), (x, y)| (total_x + x, total_y + y))
+        }
+    }
+
+    #[cfg(not(feature = "unique_windows"))]
+    /// Returns the new window size if the window was resized or its scale factor changed this step.
+    ///
+    /// Zero sizes (e.g. when the window is minimized) are ignored,
+    /// as a `wgpu` surface cannot be configured with them.
+    pub fn surface_needs_reconfigure(&self) -> Option<PhysicalSize<u32>> {
+        reconfigure_size(&self.data.window)
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Returns the new size of the given window if it was resized or its scale factor changed this step.
+    ///
+    /// Zero sizes (e.g. when the window is minimized) are ignored,
+    /// as a `wgpu` surface cannot be configured with them.
+    pub fn surface_needs_reconfigure(&self, window_id: WindowId) -> Option<PhysicalSize<u32>> {
+        reconfigure_size(&self.data.windows[window_id])
+    }
+
+    /// Returns the cause of the most recent [Event::NewEvents], or `None` if none has been received yet
+    pub fn last_start_cause(&self) -> Option<StartCause> {
+        self.last_start_cause
+    }
+
+    /// Returns `true` if the most recent [Event::NewEvents] was caused by a
+    /// [ControlFlow::WaitUntil] timer running out, i.e. [StartCause::ResumeTimeReached]
+    pub fn timer_elapsed(&self) -> bool {
+        matches!(self.last_start_cause, Some(StartCause::ResumeTimeReached { .. }))
+    }
+
+    #[cfg(not(feature = "unique_windows"))]
+    /// Returns the last known focus state of the window, which is `true` until the window loses focus.
+    pub fn focused(&self) -> bool {
+        self.data.window.has_focus()
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Returns the last known focus state of the given window, which is `true` until the window loses focus.
+    pub fn focused(&self, window_id: WindowId) -> bool {
+        self.data.windows[window_id].has_focus()
+    }
+
+    #[cfg(not(feature = "unique_windows"))]
+    /// Returns the last known occlusion state of the window, which is `false` until the window is occluded.
+    pub fn occluded(&self) -> bool {
+        self.data.window.currently_occluded
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Returns the last known occlusion state of the given window, which is `false` until the window is occluded.
+    pub fn occluded(&self, window_id: WindowId) -> bool {
+        self.data.windows[window_id].currently_occluded
+    }
+
+    /// Returns the current cursor position, or `None` if the cursor is not inside a window
+    /// or has not moved since it entered.
+    pub fn cursor_position(&self) -> Option<PhysicalPosition<f64>> {
+        #[cfg(not(feature = "unique_windows"))]
+        {
+            self.data.window.cursor_position
+        }
+        #[cfg(feature = "unique_windows")]
+        {
+            self.data
+                .windows
+                .values()
+                .find_map(|window| window.cursor_position)
+        }
+    }
+
+    /// Returns the path length in physical pixels the cursor travelled in all windows this step
+    pub fn cursor_travel_this_step(&self) -> f64 {
+        #[cfg(not(feature = "unique_windows"))]
+        {
+            self.data.window.cursor_travel_this_step()
+        }
+        #[cfg(feature = "unique_windows")]
+        {
+            self.data
+                .windows
+                .values()
+                .map(WindowCallbackData::cursor_travel_this_step)
+                .sum()
+        }
+    }
+
+    /// Returns the path length in physical pixels the cursor travelled in all windows since the start
+    pub fn total_cursor_travel(&self) -> f64 {
+        self.total_cursor_travel
+    }
+
+    /// Returns true if the given input-modifier combination is pressed across the window and device inputs.
+    ///
+    /// Each input counts as pressed if it is pressed in any window or device [InputData],
+    /// and the modifiers are the union of the modifiers of all of them.
+    /// This way a combination matches even when some of its inputs only arrived as device events.
+    pub fn pressed_combination_any_source<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> bool {
+        let sources = self.input_sources();
+
+        inputs
+            .into_iter()
+            .map(|input| input.into())
+            .all(|input| sources.iter().any(|source| source.pressed(input)))
+            && sources
+                .iter()
+                .fold(Modifiers::empty(), |union, source| union | source.modifiers())
+                .contains(modifiers)
+    }
+
+    /// Returns the window and device inputs that were pressed and released this step
+    /// and whether the modifiers changed, see [InputData::changes].
+    ///
+    /// Each input is listed once, even if it was pressed in several windows or both as a window and a device input.
+    pub fn input_changes(&self) -> InputChanges {
+        self.input_sources()
+            .into_iter()
+            .map(InputData::changes)
+            .fold(InputChanges::default(), |mut total, changes| {
+                for input in changes.newly_pressed {
+                    if !total.newly_pressed.contains(&input) {
+                        total.newly_pressed.push(input);
+                    }
+                }
+                for input in changes.newly_released {
+                    if !total.newly_released.contains(&input) {
+                        total.newly_released.push(input);
+                    }
+                }
+                total.modifiers_changed |= changes.modifiers_changed;
+                total
+            })
+    }
+
+    /// Returns true if any window or device input was pressed or released this step, see [InputData::changed]
+    ///
+    /// Useful for skipping work, such as redrawing a user interface, on steps without input.
+    pub fn input_changed(&self) -> bool {
+        self.input_sources().into_iter().any(InputData::changed)
+    }
+
+    /// Returns the [InputData] of all windows and devices
+    fn input_sources(&self) -> Vec<&InputData> {
+        #[cfg(not(feature = "unique_windows"))]
+        let windows = std::iter::once(&self.data.window.inputs);
+        #[cfg(feature = "unique_windows")]
+        let windows = self.data.windows.values().map(|window| &window.inputs);
+
+        #[cfg(not(feature = "unique_devices"))]
+        let devices = std::iter::once(&self.data.device.inputs);
+        #[cfg(feature = "unique_devices")]
+        let devices = self.data.devices.values().map(|device| &device.inputs);
+
+        windows.chain(devices).collect()
+    }
+
+    /// Returns true if Return or NumpadEnter was just pressed
+    pub fn confirm_pressed(&self) -> bool {
+        self.window_key_just_pressed(&[VirtualKeyCode::Return, VirtualKeyCode::NumpadEnter])
+    }
+
+    /// Returns true if Escape was just pressed
+    pub fn cancel_pressed(&self) -> bool {
+        self.window_key_just_pressed(&[VirtualKeyCode::Escape])
+    }
+
+    #[cfg(not(feature = "unique_windows"))]
+    fn window_key_just_pressed(&self, keys: &[VirtualKeyCode]) -> bool {
+        self.data.window.inputs.just_pressed_any(keys.iter().copied())
+    }
+
+    #[cfg(feature = "unique_windows")]
+    /// Checks all windows, as dialogs can be in any of them.
+    fn window_key_just_pressed(&self, keys: &[VirtualKeyCode]) -> bool {
+        self.data
+            .windows
+            .values()
+            .any(|window| window.inputs.just_pressed_any(keys.iter().copied()))
+    }
+}
+
+fn per_second(duration: Duration, count: usize) -> f64 {
+    if duration.is_zero() {
+        return 0.0;
+    }
+    count as f64 / duration.as_secs_f64()
+}
+
+/// Returns the latest size of the window if it was resized or its scale factor changed this step,
+/// which is the new inner size of the [WindowEvent::ScaleFactorChanged] if that came last.
+fn reconfigure_size(window: &WindowCallbackData) -> Option<PhysicalSize<u32>> {
+    if window.resized.is_none() && window.scale_factor.is_none() {
+        return None;
+    }
+
+    window
+        .size
+        .filter(|size| size.width != 0 && size.height != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::{
+        dpi::PhysicalPosition,
+        event::{DeviceEvent, ElementState},
+    };
+
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn callbacks_read_and_write_event_helper_data() {
+        let mut eh = EventHelper::new(None);
+        let mut callbacks = Callbacks::<Option<PhysicalPosition<f64>>>::empty();
+        callbacks.window.cursor_moved(|eh, _| {
+            eh.user_data = eh.cursor_position();
+            eh.data.window.track_cursor_path = true;
+        });
+
+        let position = PhysicalPosition::new(3.0, 4.0);
+        testing::feed_cursor(&mut eh, &callbacks, position);
+        testing::step(&mut eh, &callbacks);
+
+        assert_eq!(eh.user_data, Some(position));
+        assert!(eh.data.window.track_cursor_path);
+
+        testing::feed_cursor(&mut eh, &callbacks, PhysicalPosition::new(5.0, 6.0));
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.data.window.cursor_path().len(), 1);
+    }
+
+    #[test]
+    fn surface_reconfigures_on_resize_but_not_when_minimized() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::Resized(PhysicalSize::new(800, 600)),
+        );
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.surface_needs_reconfigure(), Some(PhysicalSize::new(800, 600)));
+
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::Resized(PhysicalSize::new(0, 0)),
+        );
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.surface_needs_reconfigure(), None);
+    }
+
+    #[test]
+    fn surface_reconfigures_with_the_new_inner_size_of_a_scale_change() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::Resized(PhysicalSize::new(800, 600)),
+        );
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::ScaleFactorChanged {
+                scale_factor: 2.0,
+                new_inner_size: &mut PhysicalSize::new(1600, 1200),
+            },
+        );
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.surface_needs_reconfigure(), Some(PhysicalSize::new(1600, 1200)));
+    }
+
+    #[test]
+    fn mouse_delta_applies_sensitivity_and_inversion() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+        eh.set_mouse_sensitivity(0.5);
+        eh.set_mouse_inverted(false, true);
+
+        testing::feed_device_event(
+            &mut eh,
+            &callbacks,
+            DeviceEvent::MouseMotion {
+                delta: (10.0, 10.0),
+            },
+        );
+        testing::step(&mut eh, &callbacks);
+
+        assert_eq!(eh.mouse_delta(), Some((5.0, -5.0)));
+        assert_eq!(eh.raw_mouse_delta(), Some((10.0, 10.0)));
+    }
+
+    #[test]
+    fn step_rate_needs_two_steps() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        std::thread::sleep(Duration::from_millis(20));
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.steps_per_second(), 0.0);
+        assert_eq!(eh.average_steps_per_second(), 0.0);
+
+        std::thread::sleep(Duration::from_millis(5));
+        testing::step(&mut eh, &callbacks);
+        assert!(eh.steps_per_second() > 0.0);
+        // only the interval since the first step is recorded, not the one since the creation
+        assert_eq!(eh.average_steps_per_second(), eh.steps_per_second());
+    }
+
+    #[test]
+    fn confirm_accepts_both_enter_keys() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        for key in [VirtualKeyCode::Return, VirtualKeyCode::NumpadEnter] {
+            testing::feed_key(&mut eh, &callbacks, key, ElementState::Pressed);
+            testing::step(&mut eh, &callbacks);
+            assert!(eh.confirm_pressed());
+            assert!(!eh.cancel_pressed());
+
+            testing::feed_key(&mut eh, &callbacks, key, ElementState::Released);
+            testing::step(&mut eh, &callbacks);
+            assert!(!eh.confirm_pressed());
+        }
+
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::Escape, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+        assert!(eh.cancel_pressed());
+        assert!(!eh.confirm_pressed());
+    }
+
+    #[test]
+    fn cursor_travel_sums_every_movement() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        // the first position has nothing to travel from
+        testing::feed_cursor(&mut eh, &callbacks, PhysicalPosition::new(0.0, 0.0));
+        testing::feed_cursor(&mut eh, &callbacks, PhysicalPosition::new(3.0, 4.0));
+        testing::feed_cursor(&mut eh, &callbacks, PhysicalPosition::new(3.0, 0.0));
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.cursor_travel_this_step(), 9.0);
+        assert_eq!(eh.total_cursor_travel(), 9.0);
+
+        testing::feed_cursor(&mut eh, &callbacks, PhysicalPosition::new(9.0, 8.0));
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.cursor_travel_this_step(), 10.0);
+        assert_eq!(eh.total_cursor_travel(), 19.0);
+
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(eh.cursor_travel_this_step(), 0.0);
+        assert_eq!(eh.total_cursor_travel(), 19.0);
+    }
+
+    #[test]
+    fn combination_matches_across_window_and_device_inputs() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::ModifiersChanged(Modifiers::CTRL),
+        );
+        testing::feed_device_key(&mut eh, &callbacks, VirtualKeyCode::S, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+
+        assert!(!eh
+            .data
+            .window
+            .inputs
+            .pressed_combination([VirtualKeyCode::S], Modifiers::CTRL));
+        assert!(eh.pressed_combination_any_source([VirtualKeyCode::S], Modifiers::CTRL));
+        assert!(!eh.pressed_combination_any_source([VirtualKeyCode::S], Modifiers::ALT));
+        assert!(!eh.pressed_combination_any_source(
+            [VirtualKeyCode::S, VirtualKeyCode::A],
+            Modifiers::CTRL
+        ));
+    }
+
+    #[test]
+    fn deferred_callbacks_run_exactly_once() {
+        let mut eh = EventHelper::new(0);
+        let callbacks = Callbacks::<i32>::empty();
+
+        eh.call_after(|eh| **eh += 1);
+        eh.call_after_delay(Duration::from_millis(200), |eh| **eh += 10);
+        assert_eq!(*eh, 0);
+
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+
+        std::thread::sleep(Duration::from_millis(250));
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 11);
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 11);
+    }
+
+    #[test]
+    fn input_changes_list_a_mixed_step() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::B, ElementState::Pressed);
+        testing::step(&mut eh, &callbacks);
+
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::A, ElementState::Pressed);
+        testing::feed_device_key(&mut eh, &callbacks, VirtualKeyCode::A, ElementState::Pressed);
+        testing::feed_key(&mut eh, &callbacks, VirtualKeyCode::B, ElementState::Released);
+        testing::feed_window_event(
+            &mut eh,
+            &callbacks,
+            WindowEvent::ModifiersChanged(Modifiers::SHIFT),
+        );
+        testing::step(&mut eh, &callbacks);
+
+        // the scan codes of the keys are listed as well
+        let changes = eh.input_changes();
+        let a = GenericInput::KeyCode(VirtualKeyCode::A);
+        let b = GenericInput::KeyCode(VirtualKeyCode::B);
+        assert_eq!(changes.newly_pressed.iter().filter(|&&input| input == a).count(), 1);
+        assert!(!changes.newly_pressed.contains(&b));
+        assert!(changes.newly_released.contains(&b));
+        assert!(!changes.newly_released.contains(&a));
+        assert!(changes.modifiers_changed);
+
+        testing::step(&mut eh, &callbacks);
+        assert!(eh.input_changes().is_empty());
+    }
 }
@@ -0,0 +1,83 @@
+use ahash::AHashMap;
+use winit::event::AxisId;
+
+use crate::definitions::CallbackCallable;
+
+#[derive(Clone, Debug, Default)]
+/// The latest value of every analog axis, e.g. of a joystick.
+///
+/// By default, only the axes that moved this step have a value.
+/// Use [AxisValues::set_keep_values] to keep the values across steps instead.
+pub struct AxisValues {
+    values: AHashMap<AxisId, f64>,
+    keep_values: bool,
+    deadzone: f64,
+}
+
+/// Applies a linear deadzone to an axis value in the range `[-1, 1]`.
+///
+/// Values within the deadzone become zero and the remaining range is rescaled to `[0, 1]`,
+/// so there is no jump at the edge of the deadzone.
+pub fn apply_deadzone(value: f64, deadzone: f64) -> f64 {
+    if value.abs() <= deadzone {
+        0.0
+    } else {
+        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+    }
+}
+
+impl<D> CallbackCallable<D> for AxisValues {
+    type CallbackStruct = ();
+}
+
+impl AxisValues {
+    pub fn update(&mut self, axis: AxisId, value: f64) {
+        self.values.insert(axis, value);
+    }
+
+    /// Returns the latest value of the given axis with the deadzone applied, or `None` if it has no value
+    ///
+    /// See [apply_deadzone].
+    pub fn value(&self, axis: AxisId) -> Option<f64> {
+        self.value_raw(axis)
+            .map(|value| apply_deadzone(value, self.deadzone))
+    }
+
+    /// Returns the latest value of the given axis as it was received, or `None` if it has no value
+    pub fn value_raw(&self, axis: AxisId) -> Option<f64> {
+        self.values.get(&axis).copied()
+    }
+
+    /// Sets the deadzone applied by [AxisValues::value]
+    ///
+    /// Defaults to zero. Values are expected to be in the range `[-1, 1]`,
+    /// which is not the case for every device.
+    pub fn set_deadzone(&mut self, deadzone: f64) {
+        self.deadzone = deadzone;
+    }
+
+    pub fn deadzone(&self) -> f64 {
+        self.deadzone
+    }
+
+    /// Returns the axes that have a value together with that value
+    pub fn iter(&self) -> impl Iterator<Item = (AxisId, f64)> + '_ {
+        self.values.iter().map(|(&axis, &value)| (axis, value))
+    }
+
+    /// Sets whether values are kept across steps
+    pub fn set_keep_values(&mut self, keep_values: bool) {
+        self.keep_values = keep_values;
+    }
+
+    pub fn keep_values(&self) -> bool {
+        self.keep_values
+    }
+
+    /// Clears the values, unless they are kept across steps
+    pub fn clear(&mut self) {
+        if !self.keep_values {
+            self.values.clear();
+        }
+    }
+}
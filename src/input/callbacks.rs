@@ -1,89 +1,315 @@
-use ahash::AHashMap;
+use std::{
+    cell::Cell,
+    hash::BuildHasher,
+    time::{Duration, Instant},
+};
 
-use crate::definitions::{GenericInput, Modifiers, CB};
+#[cfg(feature = "boxed_callbacks")]
+use std::{cell::RefCell, rc::Rc};
+
+use ahash::{AHashMap, AHashSet, RandomState};
+
+use winit::event::ElementState;
+
+use crate::definitions::{GenericInput, KeyCode, ModifierMatch, Modifiers, MouseButton, CB, CBI};
+
+#[cfg(feature = "boxed_callbacks")]
+use crate::EventHelper;
+
+/// A callback for [InputCallbacks::held_for] together with the press instant it last activated for,
+/// so it only activates once per press.
+pub type HeldCallback<D> = (CB<D>, Cell<Option<Instant>>);
+
+/// A callback for [InputCallbacks::repeat] together with the press instant it last activated for
+/// and how many times it activated for that press.
+pub type RepeatCallback<D> = (CB<D>, Cell<Option<(Instant, u32)>>);
+
+/// A combination callback together with its priority and registration order.
+///
+/// Matching callbacks activate in order of descending priority, then specificity and then registration order,
+/// see [InputCallbacks::pressed_combination_with_priority].
+pub type PrioritizedCallback<D> = (CB<D>, i32, u64);
+
+/// A callback for [InputCallbacks::just_pressed_where] or [InputCallbacks::just_released_where]
+/// together with the predicate that selects its inputs.
+pub type FilteredCallback<D> = (fn(GenericInput) -> bool, CBI<D, GenericInput>);
+
+/// A callback for [InputCallbacks::keyboard_input_filtered] together with the predicate that gates it.
+pub type KeyboardFilteredCallback<D> = (
+    fn((KeyCode, ElementState)) -> bool,
+    CBI<D, (KeyCode, ElementState)>,
+);
+
+/// A callback for [InputCallbacks::just_pressed_any_with] or [InputCallbacks::just_released_any_with]
+/// together with the inputs it activates for.
+pub type AnyOfCallback<D, S = RandomState> = (AHashSet<GenericInput, S>, CBI<D, GenericInput>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The kind of combination callback a binding belongs to.
+pub enum BindingKind {
+    Pressed,
+    JustPressed,
+    JustReleased,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// Identifies a combination callback registered on [InputCallbacks].
+///
+/// Returned by the functions that register a single combination, see [InputCallbacks::remove].
+pub struct BindingHandle {
+    kind: BindingKind,
+    key: (Vec<GenericInput>, ModifierMatch),
+    /// The registration order of the callback, which tells it apart from later callbacks for the same combination.
+    order: u64,
+}
+
+impl BindingHandle {
+    pub fn kind(&self) -> BindingKind {
+        self.kind
+    }
+
+    /// Returns the inputs of the binding in sorted order
+    pub fn inputs(&self) -> &[GenericInput] {
+        &self.key.0
+    }
+
+    pub fn modifiers(&self) -> ModifierMatch {
+        self.key.1
+    }
+}
 
 /// A storage medium for input callbacks.
 ///
 /// Inputs are keyboard keys and mouse buttons.
-pub struct InputCallbacks<D> {
-    pub pressed: AHashMap<(Vec<GenericInput>, Modifiers), CB<D>>,
-    pub just_pressed: AHashMap<(Vec<GenericInput>, Modifiers), CB<D>>,
-    pub just_released: AHashMap<(Vec<GenericInput>, Modifiers), CB<D>>,
+///
+/// The maps use the same hasher as [InputData](crate::input::InputData), see [InputCallbacks::with_hasher].
+pub struct InputCallbacks<D, S = RandomState> {
+    pub pressed: AHashMap<(Vec<GenericInput>, ModifierMatch), PrioritizedCallback<D>, S>,
+    pub just_pressed: AHashMap<(Vec<GenericInput>, ModifierMatch), PrioritizedCallback<D>, S>,
+    pub just_released: AHashMap<(Vec<GenericInput>, ModifierMatch), PrioritizedCallback<D>, S>,
+    pub sequences: AHashMap<(Vec<GenericInput>, Duration), CB<D>, S>,
+    pub double_clicks: AHashMap<(MouseButton, Duration), CB<D>, S>,
+    pub held: AHashMap<(GenericInput, Duration), HeldCallback<D>, S>,
+    pub repeats: AHashMap<(GenericInput, Duration, Duration), RepeatCallback<D>, S>,
+    pub just_pressed_filtered: Vec<FilteredCallback<D>>,
+    pub just_released_filtered: Vec<FilteredCallback<D>>,
+    pub keyboard_input_filtered: Vec<KeyboardFilteredCallback<D>>,
+    pub just_pressed_any_with: Vec<AnyOfCallback<D, S>>,
+    pub just_released_any_with: Vec<AnyOfCallback<D, S>>,
+    /// The registration order given to the next combination callback.
+    next_order: u64,
 }
 
-impl<D> Clone for InputCallbacks<D> {
+#[cfg(not(feature = "boxed_callbacks"))]
+impl<D, S: Clone> Clone for InputCallbacks<D, S> {
     fn clone(&self) -> Self {
         Self {
             pressed: self.pressed.clone(),
             just_pressed: self.just_pressed.clone(),
             just_released: self.just_released.clone(),
+            sequences: self.sequences.clone(),
+            double_clicks: self.double_clicks.clone(),
+            held: self.held.clone(),
+            repeats: self.repeats.clone(),
+            just_pressed_filtered: self.just_pressed_filtered.clone(),
+            just_released_filtered: self.just_released_filtered.clone(),
+            keyboard_input_filtered: self.keyboard_input_filtered.clone(),
+            just_pressed_any_with: self.just_pressed_any_with.clone(),
+            just_released_any_with: self.just_released_any_with.clone(),
+            next_order: self.next_order,
         }
     }
 }
 
-impl<D> Default for InputCallbacks<D> {
+impl<D, S: BuildHasher + Clone + Default> Default for InputCallbacks<D, S> {
     fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<D, S: BuildHasher + Clone> InputCallbacks<D, S> {
+    /// Creates empty callbacks whose maps use the given hasher, see [InputData::with_hasher](crate::input::InputData::with_hasher)
+    pub fn with_hasher(hasher: S) -> Self {
         Self {
-            pressed: Default::default(),
-            just_pressed: Default::default(),
-            just_released: Default::default(),
+            pressed: AHashMap::with_hasher(hasher.clone()),
+            just_pressed: AHashMap::with_hasher(hasher.clone()),
+            just_released: AHashMap::with_hasher(hasher.clone()),
+            sequences: AHashMap::with_hasher(hasher.clone()),
+            double_clicks: AHashMap::with_hasher(hasher.clone()),
+            held: AHashMap::with_hasher(hasher.clone()),
+            repeats: AHashMap::with_hasher(hasher),
+            just_pressed_filtered: Vec::new(),
+            just_released_filtered: Vec::new(),
+            keyboard_input_filtered: Vec::new(),
+            just_pressed_any_with: Vec::new(),
+            just_released_any_with: Vec::new(),
+            next_order: 0,
         }
     }
-}
 
-impl<D> InputCallbacks<D> {
     /// Adds a callback that will activate constantly while the given input is pressed,
     /// overwriting existing callbacks for the same keybinds.
-    pub fn pressed<I: Into<GenericInput>>(&mut self, input: I, callback: CB<D>) {
-        self.pressed_combination([input.into()], Modifiers::empty(), callback);
+    pub fn pressed<I: Into<GenericInput>>(
+        &mut self,
+        input: I,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.pressed_combination([input.into()], Modifiers::empty(), callback)
     }
 
     /// Adds a callback that will activate when the given input was just pressed,
     /// overwriting existing callbacks for the same keybinds.
-    pub fn just_pressed<I: Into<GenericInput>>(&mut self, input: I, callback: CB<D>) {
-        self.just_pressed_combination([input.into()], Modifiers::empty(), callback);
+    pub fn just_pressed<I: Into<GenericInput>>(
+        &mut self,
+        input: I,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_pressed_combination([input.into()], Modifiers::empty(), callback)
     }
 
     /// Adds a callback that will activate when the given input was just released,
     /// overwriting existing callbacks for the same keybinds.
-    pub fn just_released<I: Into<GenericInput>>(&mut self, input: I, callback: CB<D>) {
-        self.just_released_combination([input.into()], Modifiers::empty(), callback);
+    pub fn just_released<I: Into<GenericInput>>(
+        &mut self,
+        input: I,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_released_combination([input.into()], Modifiers::empty(), callback)
     }
 
     /// Adds a callback that will activate constantly while any of the given inputs is pressed,
     /// overwriting existing callbacks for the same keybinds.
+    ///
+    /// Returns a handle for the binding of every input, see [InputCallbacks::remove].
     pub fn pressed_any<I: Into<GenericInput>>(
         &mut self,
         inputs: impl IntoIterator<Item = I>,
-        callback: CB<D>,
-    ) {
-        inputs.into_iter().for_each(|input| {
-            self.pressed_combination([input.into()], Modifiers::empty(), callback);
-        });
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> Vec<BindingHandle> {
+        let callback = share_callback(callback);
+        inputs
+            .into_iter()
+            .map(|input| {
+                self.insert_binding(
+                    BindingKind::Pressed,
+                    binding([input], Modifiers::empty()),
+                    callback(),
+                    0,
+                )
+            })
+            .collect()
     }
 
     /// Adds a callback that will activate when any of the given inputs was just pressed,
     /// overwriting existing callbacks for the same keybinds.
+    ///
+    /// Returns a handle for the binding of every input, see [InputCallbacks::remove].
     pub fn just_pressed_any<I: Into<GenericInput>>(
         &mut self,
         inputs: impl IntoIterator<Item = I>,
-        callback: CB<D>,
-    ) {
-        inputs.into_iter().for_each(|input| {
-            self.just_pressed_combination([input.into()], Modifiers::empty(), callback);
-        });
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> Vec<BindingHandle> {
+        let callback = share_callback(callback);
+        inputs
+            .into_iter()
+            .map(|input| {
+                self.insert_binding(
+                    BindingKind::JustPressed,
+                    binding([input], Modifiers::empty()),
+                    callback(),
+                    0,
+                )
+            })
+            .collect()
     }
 
     /// Adds a callback that will activate when any of the given inputs was just released,
     /// overwriting existing callbacks for the same keybinds.
+    ///
+    /// Returns a handle for the binding of every input, see [InputCallbacks::remove].
     pub fn just_released_any<I: Into<GenericInput>>(
         &mut self,
         inputs: impl IntoIterator<Item = I>,
-        callback: CB<D>,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> Vec<BindingHandle> {
+        let callback = share_callback(callback);
+        inputs
+            .into_iter()
+            .map(|input| {
+                self.insert_binding(
+                    BindingKind::JustReleased,
+                    binding([input], Modifiers::empty()),
+                    callback(),
+                    0,
+                )
+            })
+            .collect()
+    }
+
+    /// Adds a callback that will activate for every just pressed input among the given inputs,
+    /// receiving the input that activated it.
+    ///
+    /// Unlike [InputCallbacks::just_pressed_any], this does not overwrite existing callbacks.
+    pub fn just_pressed_any_with<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        callback: crate::__callback_arg!(CBI<D, GenericInput>),
+    ) {
+        let inputs = self.input_set(inputs);
+        self.just_pressed_any_with
+            .push((inputs, crate::__store_callback!(callback)));
+    }
+
+    /// Adds a callback that will activate for every just released input among the given inputs,
+    /// receiving the input that activated it.
+    ///
+    /// Unlike [InputCallbacks::just_released_any], this does not overwrite existing callbacks.
+    pub fn just_released_any_with<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        callback: crate::__callback_arg!(CBI<D, GenericInput>),
+    ) {
+        let inputs = self.input_set(inputs);
+        self.just_released_any_with
+            .push((inputs, crate::__store_callback!(callback)));
+    }
+
+    /// Adds a callback that will activate for every just pressed input the predicate returns true for.
+    ///
+    /// This avoids registering a callback for every key in a group, such as all digit keys.
+    pub fn just_pressed_where(
+        &mut self,
+        predicate: fn(GenericInput) -> bool,
+        callback: crate::__callback_arg!(CBI<D, GenericInput>),
+    ) {
+        self.just_pressed_filtered
+            .push((predicate, crate::__store_callback!(callback)));
+    }
+
+    /// Adds a callback that will activate for every just released input the predicate returns true for.
+    ///
+    /// This avoids registering a callback for every key in a group, such as all digit keys.
+    pub fn just_released_where(
+        &mut self,
+        predicate: fn(GenericInput) -> bool,
+        callback: crate::__callback_arg!(CBI<D, GenericInput>),
+    ) {
+        self.just_released_filtered
+            .push((predicate, crate::__store_callback!(callback)));
+    }
+
+    /// Adds a callback that will activate for every just pressed or just released key
+    /// the predicate returns true for, receiving the key and whether it was pressed or released.
+    ///
+    /// Unlike [InputCallbacks::just_pressed_where], the predicate can depend on the state of the key,
+    /// and mouse buttons are ignored.
+    pub fn keyboard_input_filtered(
+        &mut self,
+        predicate: fn((KeyCode, ElementState)) -> bool,
+        callback: crate::__callback_arg!(CBI<D, (KeyCode, ElementState)>),
     ) {
-        inputs.into_iter().for_each(|input| {
-            self.just_released_combination([input.into()], Modifiers::empty(), callback);
-        });
+        self.keyboard_input_filtered
+            .push((predicate, crate::__store_callback!(callback)));
     }
 
     /// Adds a callback that will activate constantly while all of the given inputs are pressed,
@@ -91,9 +317,9 @@ impl<D> InputCallbacks<D> {
     pub fn pressed_all<I: Into<GenericInput>>(
         &mut self,
         inputs: impl IntoIterator<Item = I>,
-        callback: CB<D>,
-    ) {
-        self.pressed_combination(inputs, Modifiers::empty(), callback);
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.pressed_combination(inputs, Modifiers::empty(), callback)
     }
 
     /// Adds a callback that will activate when all of the given inputs were just pressed,
@@ -101,9 +327,9 @@ impl<D> InputCallbacks<D> {
     pub fn just_pressed_all<I: Into<GenericInput>>(
         &mut self,
         inputs: impl IntoIterator<Item = I>,
-        callback: CB<D>,
-    ) {
-        self.just_pressed_combination(inputs, Modifiers::empty(), callback);
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_pressed_combination(inputs, Modifiers::empty(), callback)
     }
 
     /// Adds a callback that will activate when all of the given inputs were just released,
@@ -111,11 +337,9 @@ impl<D> InputCallbacks<D> {
     pub fn just_released_all<I: Into<GenericInput>>(
         &mut self,
         inputs: impl IntoIterator<Item = I>,
-        callback: CB<D>,
-    ) {
-        inputs.into_iter().for_each(|input| {
-            self.just_released_combination([input.into()], Modifiers::empty(), callback);
-        });
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_released_combination(inputs, Modifiers::empty(), callback)
     }
 
     /// Adds a callback that will activate constantly while the given input-modifier combination is pressed.
@@ -127,15 +351,66 @@ impl<D> InputCallbacks<D> {
         &mut self,
         inputs: impl IntoIterator<Item = I>,
         modifiers: Modifiers,
-        callback: CB<D>,
-    ) {
-        self.pressed.insert(
-            (
-                inputs.into_iter().map(|input| input.into()).collect(),
-                modifiers,
-            ),
-            callback,
-        );
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.pressed_combination_with_priority(inputs, modifiers, 0, callback)
+    }
+
+    /// Adds a callback with the given priority that will activate constantly while the given input-modifier combination is pressed.
+    ///
+    /// Callbacks with a higher priority activate first.
+    /// Callbacks with the same priority are ordered by specificity, so a more specific binding
+    /// like `Ctrl+S` runs before a general one like `S`: first by descending number of inputs,
+    /// then by descending number of required modifiers, then by descending number of forbidden modifiers.
+    /// Callbacks that are equally specific activate in the order they were registered in.
+    ///
+    /// Calling [EventHelper::consume_input](crate::EventHelper::consume_input) from a callback
+    /// stops the callbacks after it from activating this step.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    ///
+    /// `modifiers` can be [Modifiers] or a [ModifierMatch].
+    pub fn pressed_combination_with_priority<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+        priority: i32,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.insert_binding(
+            BindingKind::Pressed,
+            binding(inputs, modifiers),
+            crate::__store_callback!(callback),
+            priority,
+        )
+    }
+
+    /// Adds a callback that will activate constantly while the given inputs are pressed while exactly the given modifiers are held.
+    ///
+    /// Unlike [InputCallbacks::pressed_combination], a callback for `Ctrl+S` does not activate for `Ctrl+Shift+S`.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    pub fn pressed_combination_exact<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.pressed_combination_with_priority(inputs, ModifierMatch::exact(modifiers), 0, callback)
+    }
+
+    /// Adds a callback that will activate constantly while the given inputs are pressed and the held modifiers match `modifiers`.
+    ///
+    /// Use a [ModifierMatch] to forbid modifiers, e.g. to only activate for `S` while alt is not held.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    pub fn pressed_combination_matching<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.pressed_combination_with_priority(inputs, modifiers, 0, callback)
     }
 
     /// Adds a callback that will activate when the given input-modifier combination is just pressed.
@@ -147,15 +422,66 @@ impl<D> InputCallbacks<D> {
         &mut self,
         inputs: impl IntoIterator<Item = I>,
         modifiers: Modifiers,
-        callback: CB<D>,
-    ) {
-        self.just_pressed.insert(
-            (
-                inputs.into_iter().map(|input| input.into()).collect(),
-                modifiers,
-            ),
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_pressed_combination_with_priority(inputs, modifiers, 0, callback)
+    }
+
+    /// Adds a callback with the given priority that will activate when the given input-modifier combination is just pressed.
+    ///
+    /// Callbacks are ordered like those of [InputCallbacks::pressed_combination_with_priority].
+    /// Calling [EventHelper::consume_input](crate::EventHelper::consume_input) from a callback
+    /// stops the callbacks after it from activating this step.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    ///
+    /// `modifiers` can be [Modifiers] or a [ModifierMatch].
+    pub fn just_pressed_combination_with_priority<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+        priority: i32,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.insert_binding(
+            BindingKind::JustPressed,
+            binding(inputs, modifiers),
+            crate::__store_callback!(callback),
+            priority,
+        )
+    }
+
+    /// Adds a callback that will activate when the given inputs were just pressed while exactly the given modifiers are held.
+    ///
+    /// Unlike [InputCallbacks::just_pressed_combination], a callback for `Ctrl+S` does not activate for `Ctrl+Shift+S`.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    pub fn just_pressed_combination_exact<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_pressed_combination_with_priority(
+            inputs,
+            ModifierMatch::exact(modifiers),
+            0,
             callback,
-        );
+        )
+    }
+
+    /// Adds a callback that will activate when the given inputs were just pressed and the held modifiers match `modifiers`.
+    ///
+    /// Use a [ModifierMatch] to forbid modifiers, e.g. to only activate for `S` while alt is not held.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    pub fn just_pressed_combination_matching<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_pressed_combination_with_priority(inputs, modifiers, 0, callback)
     }
 
     /// Adds a callback that will activate when the given input-modifier combination is just released.
@@ -167,14 +493,359 @@ impl<D> InputCallbacks<D> {
         &mut self,
         inputs: impl IntoIterator<Item = I>,
         modifiers: Modifiers,
-        callback: CB<D>,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_released_combination_with_priority(inputs, modifiers, 0, callback)
+    }
+
+    /// Adds a callback with the given priority that will activate when the given input-modifier combination is just released.
+    ///
+    /// Callbacks are ordered like those of [InputCallbacks::pressed_combination_with_priority].
+    /// Calling [EventHelper::consume_input](crate::EventHelper::consume_input) from a callback
+    /// stops the callbacks after it from activating this step.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    ///
+    /// `modifiers` can be [Modifiers] or a [ModifierMatch].
+    pub fn just_released_combination_with_priority<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+        priority: i32,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.insert_binding(
+            BindingKind::JustReleased,
+            binding(inputs, modifiers),
+            crate::__store_callback!(callback),
+            priority,
+        )
+    }
+
+    /// Adds a callback that will activate when the given inputs were just released while exactly the given modifiers are held.
+    ///
+    /// Unlike [InputCallbacks::just_released_combination], a callback for `Ctrl+S` does not activate for `Ctrl+Shift+S`.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    pub fn just_released_combination_exact<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_released_combination_with_priority(
+            inputs,
+            ModifierMatch::exact(modifiers),
+            0,
+            callback,
+        )
+    }
+
+    /// Adds a callback that will activate when the given inputs were just released and the held modifiers match `modifiers`.
+    ///
+    /// Use a [ModifierMatch] to forbid modifiers, e.g. to only activate for `S` while alt is not held.
+    ///
+    /// Overwrites any previous callback for the same combination.
+    pub fn just_released_combination_matching<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+        callback: crate::__callback_arg!(CB<D>),
+    ) -> BindingHandle {
+        self.just_released_combination_with_priority(inputs, modifiers, 0, callback)
+    }
+
+    /// Adds a callback that will activate when the given inputs were pressed in order within the given duration.
+    ///
+    /// Overwrites any previous callback for the same sequence and duration.
+    ///
+    /// See [InputData::just_completed_sequence](crate::input::InputData::just_completed_sequence).
+    pub fn sequence<I: Into<GenericInput>>(
+        &mut self,
+        sequence: impl IntoIterator<Item = I>,
+        within: Duration,
+        callback: crate::__callback_arg!(CB<D>),
     ) {
-        self.just_released.insert(
+        self.sequences.insert(
             (
-                inputs.into_iter().map(|input| input.into()).collect(),
-                modifiers,
+                sequence.into_iter().map(|input| input.into()).collect(),
+                within,
             ),
-            callback,
+            crate::__store_callback!(callback),
         );
     }
+
+    /// Adds a callback that will activate when the given button is pressed twice within the given duration.
+    ///
+    /// Overwrites any previous callback for the same button and duration.
+    ///
+    /// See [InputData::double_clicked](crate::input::InputData::double_clicked).
+    pub fn double_clicked(
+        &mut self,
+        button: MouseButton,
+        within: Duration,
+        callback: crate::__callback_arg!(CB<D>),
+    ) {
+        self.double_clicks
+            .insert((button, within), crate::__store_callback!(callback));
+    }
+
+    /// Adds a callback that will activate once when the given input has been held for the given duration.
+    ///
+    /// Releasing and pressing the input again allows the callback to activate again.
+    ///
+    /// Overwrites any previous callback for the same input and duration.
+    pub fn held_for<I: Into<GenericInput>>(
+        &mut self,
+        input: I,
+        duration: Duration,
+        callback: crate::__callback_arg!(CB<D>),
+    ) {
+        self.held.insert(
+            (input.into(), duration),
+            (crate::__store_callback!(callback), Cell::new(None)),
+        );
+    }
+
+    /// Returns the kind, inputs and modifiers of every registered combination callback.
+    ///
+    /// The inputs of every binding are in sorted order.
+    pub fn bindings(&self) -> impl Iterator<Item = (BindingKind, &[GenericInput], ModifierMatch)> {
+        map_bindings(BindingKind::Pressed, &self.pressed)
+            .chain(map_bindings(BindingKind::JustPressed, &self.just_pressed))
+            .chain(map_bindings(BindingKind::JustReleased, &self.just_released))
+    }
+
+    /// Adds a callback that will activate when the given input was just pressed,
+    /// again after it has been held for `initial_delay`, and then every `interval` while it is held.
+    ///
+    /// Activates at most once per step. Releasing the input resets the timer.
+    ///
+    /// The timer is measured from the first press, so the repeated key presses some platforms send
+    /// while a key is held do not affect the repeat rate.
+    ///
+    /// Overwrites any previous callback for the same input, delay and interval.
+    pub fn repeat<I: Into<GenericInput>>(
+        &mut self,
+        input: I,
+        initial_delay: Duration,
+        interval: Duration,
+        callback: crate::__callback_arg!(CB<D>),
+    ) {
+        self.repeats.insert(
+            (input.into(), initial_delay, interval),
+            (crate::__store_callback!(callback), Cell::new(None)),
+        );
+    }
+
+    /// Removes the callback the handle was returned for, returning it if it existed.
+    ///
+    /// If the combination was registered again since, the newer callback is kept and `None` is returned.
+    pub fn remove(&mut self, handle: &BindingHandle) -> Option<CB<D>> {
+        let bindings = self.bindings_mut(handle.kind);
+
+        if bindings
+            .get(&handle.key)
+            .is_some_and(|(_, _, order)| *order == handle.order)
+        {
+            bindings.remove(&handle.key).map(|(callback, ..)| callback)
+        } else {
+            None
+        }
+    }
+
+    fn bindings_mut(
+        &mut self,
+        kind: BindingKind,
+    ) -> &mut AHashMap<(Vec<GenericInput>, ModifierMatch), PrioritizedCallback<D>, S> {
+        match kind {
+            BindingKind::Pressed => &mut self.pressed,
+            BindingKind::JustPressed => &mut self.just_pressed,
+            BindingKind::JustReleased => &mut self.just_released,
+        }
+    }
+
+    /// Collects the given inputs into a set that uses the hasher of the maps.
+    fn input_set<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+    ) -> AHashSet<GenericInput, S> {
+        let mut set = AHashSet::with_hasher(self.pressed.hasher().clone());
+        set.extend(inputs.into_iter().map(Into::into));
+        set
+    }
+
+    /// Inserts a combination callback, giving it the next registration order.
+    fn insert_binding(
+        &mut self,
+        kind: BindingKind,
+        key: (Vec<GenericInput>, ModifierMatch),
+        callback: CB<D>,
+        priority: i32,
+    ) -> BindingHandle {
+        let order = self.next_order;
+        self.next_order += 1;
+        self.bindings_mut(kind)
+            .insert(key.clone(), (callback, priority, order));
+
+        BindingHandle { kind, key, order }
+    }
+
+    /// Removes the `pressed` callback for the given input-modifier combination, returning it if it existed.
+    pub fn remove_pressed<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+    ) -> Option<CB<D>> {
+        self.pressed
+            .remove(&binding(inputs, modifiers))
+            .map(|(callback, ..)| callback)
+    }
+
+    /// Removes the `just_pressed` callback for the given input-modifier combination, returning it if it existed.
+    pub fn remove_just_pressed<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+    ) -> Option<CB<D>> {
+        self.just_pressed
+            .remove(&binding(inputs, modifiers))
+            .map(|(callback, ..)| callback)
+    }
+
+    /// Removes the `just_released` callback for the given input-modifier combination, returning it if it existed.
+    pub fn remove_just_released<I: Into<GenericInput>>(
+        &mut self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+    ) -> Option<CB<D>> {
+        self.just_released
+            .remove(&binding(inputs, modifiers))
+            .map(|(callback, ..)| callback)
+    }
+}
+
+/// Converts the given inputs and modifiers into the key used by the callback maps.
+///
+/// The inputs are sorted and deduplicated, so the same combination always results in the same key
+/// regardless of the order the inputs are given in.
+fn binding<I: Into<GenericInput>>(
+    inputs: impl IntoIterator<Item = I>,
+    modifiers: impl Into<ModifierMatch>,
+) -> (Vec<GenericInput>, ModifierMatch) {
+    let mut inputs: Vec<GenericInput> = inputs.into_iter().map(|input| input.into()).collect();
+    inputs.sort_unstable();
+    inputs.dedup();
+
+    (inputs, modifiers.into())
+}
+
+/// Returns the kind, inputs and modifiers of every binding in one of the combination callback maps.
+fn map_bindings<D, S>(
+    kind: BindingKind,
+    map: &AHashMap<(Vec<GenericInput>, ModifierMatch), PrioritizedCallback<D>, S>,
+) -> impl Iterator<Item = (BindingKind, &[GenericInput], ModifierMatch)> {
+    map.keys()
+        .map(move |(inputs, modifiers)| (kind, inputs.as_slice(), *modifiers))
+}
+
+#[cfg(not(feature = "boxed_callbacks"))]
+/// Allows a single callback to be registered for multiple keybinds.
+fn share_callback<D>(callback: CB<D>) -> impl Fn() -> CB<D> {
+    move || callback
+}
+
+#[cfg(feature = "boxed_callbacks")]
+/// Allows a single closure to be registered for multiple keybinds.
+fn share_callback<D>(callback: impl FnMut(&mut EventHelper<D>) + 'static) -> impl Fn() -> CB<D> {
+    let callback = Rc::new(RefCell::new(callback));
+
+    move || {
+        let callback = callback.clone();
+        RefCell::new(Box::new(move |event_helper: &mut EventHelper<D>| {
+            (callback.borrow_mut())(event_helper)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::VirtualKeyCode;
+
+    use super::*;
+
+    #[test]
+    fn just_released_all_registers_a_single_combination() {
+        let mut callbacks = InputCallbacks::<()>::default();
+        let handle = callbacks.just_released_all([VirtualKeyCode::B, VirtualKeyCode::A], |_| {});
+
+        assert_eq!(handle.kind(), BindingKind::JustReleased);
+        assert_eq!(
+            handle.inputs(),
+            binding([VirtualKeyCode::A, VirtualKeyCode::B], Modifiers::empty()).0
+        );
+        assert_eq!(callbacks.just_released.len(), 1);
+        assert!(callbacks.remove(&handle).is_some());
+        assert!(callbacks.just_released.is_empty());
+    }
+
+    #[test]
+    fn any_handles_remove_every_binding() {
+        let mut callbacks = InputCallbacks::<()>::default();
+        let handles = callbacks.just_pressed_any([VirtualKeyCode::A, VirtualKeyCode::B], |_| {});
+
+        assert_eq!(handles.len(), 2);
+        handles
+            .iter()
+            .for_each(|handle| assert!(callbacks.remove(handle).is_some()));
+        assert!(callbacks.just_pressed.is_empty());
+    }
+
+    #[test]
+    fn combinations_are_independent_of_input_order() {
+        use winit::event::MouseButton;
+
+        let inputs: [GenericInput; 3] = [
+            VirtualKeyCode::A.into(),
+            MouseButton::Left.into(),
+            VirtualKeyCode::LShift.into(),
+        ];
+        let orders = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        let mut callbacks = InputCallbacks::<()>::default();
+        for order in orders {
+            callbacks.pressed_combination(order.map(|i| inputs[i]), Modifiers::CTRL, |_| {});
+        }
+        assert_eq!(callbacks.pressed.len(), 1);
+
+        for order in orders {
+            callbacks.just_pressed_combination(order.map(|i| inputs[i]), Modifiers::CTRL, |_| {});
+            let shuffled = orders[5 - order[0]].map(|i| inputs[i]);
+            assert!(callbacks
+                .remove_just_pressed(shuffled, Modifiers::CTRL)
+                .is_some());
+        }
+        assert!(callbacks.just_pressed.is_empty());
+    }
+
+    #[test]
+    fn stale_handle_does_not_remove_a_newer_binding() {
+        let mut callbacks = InputCallbacks::<()>::default();
+        let stale = callbacks.just_pressed(VirtualKeyCode::A, |_| {});
+        let current = callbacks.just_pressed(VirtualKeyCode::A, |_| {});
+
+        assert!(callbacks.remove(&stale).is_none());
+        assert_eq!(callbacks.just_pressed.len(), 1);
+
+        assert!(callbacks.remove(&current).is_some());
+        assert!(callbacks.just_pressed.is_empty());
+        assert!(callbacks.remove(&current).is_none());
+    }
 }
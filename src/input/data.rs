@@ -1,43 +1,69 @@
 use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, VecDeque},
+    hash::{BuildHasher, Hash},
     ops::{Deref, DerefMut},
     time::{Duration, Instant},
 };
 
-use ahash::{AHashMap, AHashSet};
-use winit::event::{DeviceId, ElementState, MouseButton, VirtualKeyCode};
+use ahash::{AHashMap, AHashSet, RandomState};
+use winit::event::{
+    DeviceId, ElementState, MouseButton, MouseScrollDelta, ScanCode, VirtualKeyCode,
+};
 
 use crate::{
     default_ahashmap::DefaultAHashMap,
-    definitions::{CallbackCallable, GenericInput, KeyCode, Modifiers},
+    definitions::{
+        CallbackCallable, GenericInput, KeyCode, LineDelta, ModifierMatch, Modifiers, PhysicalKey,
+        PixelDelta,
+    },
     EventHelper,
 };
 
-use super::callbacks::InputCallbacks;
+#[cfg(feature = "gamepad")]
+use crate::definitions::GamepadAxis;
+
+#[cfg(feature = "gamepad")]
+use super::axes::apply_deadzone;
 
-pub struct InputDataWithId(DefaultAHashMap<DeviceId, InputData>);
+use super::callbacks::{
+    AnyOfCallback, FilteredCallback, InputCallbacks, KeyboardFilteredCallback, PrioritizedCallback,
+};
 
-impl Default for InputDataWithId {
+#[derive(Clone)]
+/// [InputData] per device.
+///
+/// Keyed by [DeviceId] by default, but any hashable key can be used.
+/// The hasher of the maps can be configured like that of [InputData], see [InputDataWithId::with_hasher].
+pub struct InputDataWithId<K: Eq + Hash = DeviceId, S: BuildHasher + Clone + Default = RandomState>(
+    DefaultAHashMap<K, InputData<S>, S>,
+);
+
+// not derived, as that would require the key to implement `Default`
+impl<K: Eq + Hash, S: BuildHasher + Clone + Default> Default for InputDataWithId<K, S> {
     fn default() -> Self {
-        Self(Default::default())
+        Self::with_hasher(S::default())
     }
 }
 
-impl Deref for InputDataWithId {
-    type Target = DefaultAHashMap<DeviceId, InputData>;
+impl<K: Eq + Hash, S: BuildHasher + Clone + Default> Deref for InputDataWithId<K, S> {
+    type Target = DefaultAHashMap<K, InputData<S>, S>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl DerefMut for InputDataWithId {
+impl<K: Eq + Hash, S: BuildHasher + Clone + Default> DerefMut for InputDataWithId<K, S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
-impl<D: Clone> CallbackCallable<D> for InputDataWithId {
-    type CallbackStruct = DefaultAHashMap<DeviceId, InputCallbacks<D>>;
+impl<D, K: Eq + Hash, S: BuildHasher + Clone + Default> CallbackCallable<D>
+    for InputDataWithId<K, S>
+{
+    type CallbackStruct = DefaultAHashMap<K, InputCallbacks<D, S>, S>;
 
     fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {
         self.iter().for_each(|(device_id, input_data)| {
@@ -46,63 +72,293 @@ impl<D: Clone> CallbackCallable<D> for InputDataWithId {
     }
 }
 
-impl InputDataWithId {
+impl<K: Eq + Hash, S: BuildHasher + Clone + Default> InputDataWithId<K, S> {
+    /// Creates an empty map whose own maps and those of its [InputData] use the given hasher,
+    /// see [InputData::with_hasher]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self(DefaultAHashMap::with_hasher(hasher))
+    }
+
+    /// Returns the input data of the given key, inserting empty input data that uses
+    /// the hasher of this map if it is not present
+    pub fn get_mut(&mut self, key: K) -> &mut InputData<S> {
+        let hasher = self.0.hasher().clone();
+        self.0
+            .get_or_insert_with(key, || InputData::with_hasher(hasher))
+    }
+
+    /// Returns the union of the input data of all keys, see [InputData::merge]
+    pub fn merged(&self) -> InputData<S> {
+        self.values().fold(
+            InputData::with_hasher(self.hasher().clone()),
+            |mut merged, data| {
+                merged.merge(data);
+                merged
+            },
+        )
+    }
+
     pub fn clear(&mut self) {
         self.values_mut().for_each(InputData::clear);
     }
 }
 
-#[derive(Clone)]
 /// A collection of data used for input callbacks.
 ///
 /// [InputCallbacks] holds the callbacks themselves.
-pub struct InputData {
-    pressed: AHashMap<GenericInput, Instant>,
-    just_pressed: AHashSet<GenericInput>,
-    just_released: AHashSet<GenericInput>,
+///
+/// The maps use the randomly seeded [RandomState] of `ahash` by default, see [InputData::with_hasher].
+pub struct InputData<S = RandomState> {
+    /// The instants the pressed inputs were first pressed at, which repeated presses do not change.
+    pressed: AHashMap<GenericInput, Instant, S>,
+    /// The instants of the latest press or repeated press of the pressed inputs, see [InputData::last_pressed_for].
+    last_pressed: AHashMap<GenericInput, Instant, S>,
+    just_pressed: AHashSet<GenericInput, S>,
+    just_released: AHashSet<GenericInput, S>,
     modifiers: Modifiers,
+    /// Whether the modifiers changed since the last [InputData::clear].
+    modifiers_changed: bool,
+    /// The most recent presses, used for detecting sequences and double clicks.
+    ///
+    /// Holds at most [SEQUENCE_BUFFER_SIZE] presses.
+    recent_presses: VecDeque<(GenericInput, Instant)>,
+    /// The instants the inputs were last released at and how long they were pressed before that,
+    /// removed when they are pressed again.
+    ///
+    /// Holds at most [RELEASE_BUFFER_SIZE] inputs.
+    recent_releases: AHashMap<GenericInput, (Instant, Duration), S>,
+    /// How long an input has to be pressed before it counts as held, see [InputData::held].
+    hold_threshold: Duration,
+    /// Whether repeated presses of an input that is already pressed are ignored, see [InputData::set_ignore_key_repeats].
+    ignore_key_repeats: bool,
+    /// The inputs tracked as toggles and their current state, see [InputData::register_toggle].
+    toggles: AHashMap<GenericInput, bool, S>,
+    /// The scroll accumulated since the last [InputData::clear].
+    scroll: (LineDelta, PixelDelta),
+    #[cfg(feature = "gamepad")]
+    /// The last known values of the gamepad axes.
+    axes: AHashMap<GamepadAxis, f32, S>,
+    #[cfg(feature = "gamepad")]
+    /// The deadzone applied to the gamepad axes, see [InputData::set_axis_deadzone].
+    axis_deadzone: f32,
 }
 
-impl<D> CallbackCallable<D> for InputData {
-    type CallbackStruct = InputCallbacks<D>;
+/// The maximum number of recent presses [InputData] remembers for sequence and double click detection.
+pub const SEQUENCE_BUFFER_SIZE: usize = 64;
+
+/// The maximum number of released inputs [InputData] remembers the release instant of, see [InputData::released_for].
+pub const RELEASE_BUFFER_SIZE: usize = 64;
+
+/// The maximum number of inputs in a layout for [InputData::to_input_frame].
+///
+/// The 4 bits of the frame after those of the inputs hold the shift, ctrl, alt and logo modifiers.
+pub const INPUT_FRAME_MAX_INPUTS: usize = 64;
+
+/// The modifiers in the order they are packed into the highest bits of an input frame.
+const INPUT_FRAME_MODIFIERS: [Modifiers; 4] = [
+    Modifiers::SHIFT,
+    Modifiers::CTRL,
+    Modifiers::ALT,
+    Modifiers::LOGO,
+];
+
+impl<D, S: BuildHasher> CallbackCallable<D> for InputData<S> {
+    type CallbackStruct = InputCallbacks<D, S>;
 
+    /// Calls the callbacks until one of them calls [EventHelper::consume_input].
+    ///
+    /// Consuming the input only affects the remaining callbacks of this [InputData].
     fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {
-        callbacks
-            .pressed
-            .iter()
-            .filter(|((inputs, modifiers), _)| self.pressed_combination(inputs.clone(), *modifiers))
-            .for_each(|(_, func)| func(event_helper));
+        event_helper.input_consumed = false;
+        self.call_callbacks_until_consumed(event_helper, callbacks);
+        event_helper.input_consumed = false;
+    }
+}
 
-        callbacks
-            .just_pressed
-            .iter()
-            .filter(|((inputs, modifiers), _)| {
-                self.just_pressed_combination(inputs.clone(), *modifiers)
-            })
-            .for_each(|(_, func)| func(event_helper));
+impl<S: BuildHasher> InputData<S> {
+    fn call_callbacks_until_consumed<D>(
+        &self,
+        event_helper: &mut EventHelper<D>,
+        callbacks: &InputCallbacks<D, S>,
+    ) {
+        call_combination_callbacks(event_helper, &callbacks.pressed, |inputs, modifiers| {
+            self.pressed_combination_matching(inputs.iter().copied(), modifiers)
+        });
 
-        callbacks
-            .just_released
-            .iter()
-            .filter(|((inputs, modifiers), _)| {
-                self.just_released_combination(inputs.clone(), *modifiers)
-            })
-            .for_each(|(_, func)| func(event_helper));
+        call_combination_callbacks(
+            event_helper,
+            &callbacks.just_pressed,
+            |inputs, modifiers| {
+                self.just_pressed_combination_matching(inputs.iter().copied(), modifiers)
+            },
+        );
+
+        call_combination_callbacks(
+            event_helper,
+            &callbacks.just_released,
+            |inputs, modifiers| {
+                self.just_released_combination_matching(inputs.iter().copied(), modifiers)
+            },
+        );
+
+        for ((sequence, within), func) in &callbacks.sequences {
+            if event_helper.input_consumed {
+                return;
+            }
+            if self.just_completed_sequence(sequence, *within) {
+                crate::__invoke_callback!(func, event_helper);
+            }
+        }
+
+        for ((button, within), func) in &callbacks.double_clicks {
+            if event_helper.input_consumed {
+                return;
+            }
+            if self.double_clicked(*button, *within) {
+                crate::__invoke_callback!(func, event_helper);
+            }
+        }
+
+        for ((input, duration), (func, fired_for)) in &callbacks.held {
+            if event_helper.input_consumed {
+                return;
+            }
+            if let Some(pressed_at) = self.held_callback_due(input, *duration, fired_for.get()) {
+                fired_for.set(Some(pressed_at));
+                crate::__invoke_callback!(func, event_helper);
+            }
+        }
+
+        for ((input, initial_delay, interval), (func, fired_for)) in &callbacks.repeats {
+            if event_helper.input_consumed {
+                return;
+            }
+            if let Some(due) =
+                self.repeat_callback_due(input, *initial_delay, *interval, fired_for.get())
+            {
+                fired_for.set(Some(due));
+                crate::__invoke_callback!(func, event_helper);
+            }
+        }
+
+        call_filtered_callbacks(
+            event_helper,
+            &callbacks.just_pressed_filtered,
+            &self.just_pressed,
+        );
+        call_filtered_callbacks(
+            event_helper,
+            &callbacks.just_released_filtered,
+            &self.just_released,
+        );
+        call_keyboard_filtered_callbacks(
+            event_helper,
+            &callbacks.keyboard_input_filtered,
+            &self.just_pressed,
+            &self.just_released,
+        );
+        call_any_of_callbacks(
+            event_helper,
+            &callbacks.just_pressed_any_with,
+            &self.just_pressed,
+        );
+        call_any_of_callbacks(
+            event_helper,
+            &callbacks.just_released_any_with,
+            &self.just_released,
+        );
     }
 }
 
-impl Default for InputData {
+impl<S: Clone> Clone for InputData<S> {
+    fn clone(&self) -> Self {
+        Self {
+            pressed: self.pressed.clone(),
+            last_pressed: self.last_pressed.clone(),
+            just_pressed: self.just_pressed.clone(),
+            just_released: self.just_released.clone(),
+            modifiers: self.modifiers,
+            modifiers_changed: self.modifiers_changed,
+            recent_presses: self.recent_presses.clone(),
+            recent_releases: self.recent_releases.clone(),
+            hold_threshold: self.hold_threshold,
+            ignore_key_repeats: self.ignore_key_repeats,
+            toggles: self.toggles.clone(),
+            scroll: self.scroll,
+            #[cfg(feature = "gamepad")]
+            axes: self.axes.clone(),
+            #[cfg(feature = "gamepad")]
+            axis_deadzone: self.axis_deadzone,
+        }
+    }
+
+    /// Reuses the allocations of `self`, unlike the derived implementation of the `ahash` maps,
+    /// as the data is copied every step before the callbacks are called.
+    fn clone_from(&mut self, source: &Self) {
+        (*self.pressed).clone_from(&source.pressed);
+        (*self.last_pressed).clone_from(&source.last_pressed);
+        (*self.just_pressed).clone_from(&source.just_pressed);
+        (*self.just_released).clone_from(&source.just_released);
+        self.modifiers = source.modifiers;
+        self.modifiers_changed = source.modifiers_changed;
+        self.recent_presses.clone_from(&source.recent_presses);
+        (*self.recent_releases).clone_from(&source.recent_releases);
+        self.hold_threshold = source.hold_threshold;
+        self.ignore_key_repeats = source.ignore_key_repeats;
+        (*self.toggles).clone_from(&source.toggles);
+        self.scroll = source.scroll;
+        #[cfg(feature = "gamepad")]
+        (*self.axes).clone_from(&source.axes);
+        #[cfg(feature = "gamepad")]
+        {
+            self.axis_deadzone = source.axis_deadzone;
+        }
+    }
+}
+
+impl<S: BuildHasher + Clone + Default> Default for InputData<S> {
     fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl InputData {
+    /// Creates empty input data that uses the default hasher, see [InputData::with_hasher]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: BuildHasher + Clone> InputData<S> {
+    /// Creates empty input data whose maps use the given hasher.
+    ///
+    /// The maps are seeded randomly by default, so their iteration order differs between runs.
+    /// A hasher with fixed seeds (see [RandomState::with_seeds]) makes it reproducible,
+    /// e.g. for tests and replays. Any other [BuildHasher] can be used as well,
+    /// e.g. a DoS-resistant one for input from untrusted sources.
+    pub fn with_hasher(hasher: S) -> Self {
         Self {
-            pressed: AHashMap::new(),
-            just_pressed: AHashSet::new(),
-            just_released: AHashSet::new(),
+            pressed: AHashMap::with_hasher(hasher.clone()),
+            last_pressed: AHashMap::with_hasher(hasher.clone()),
+            just_pressed: AHashSet::with_hasher(hasher.clone()),
+            just_released: AHashSet::with_hasher(hasher.clone()),
             modifiers: Modifiers::empty(),
+            modifiers_changed: false,
+            hold_threshold: Duration::ZERO,
+            ignore_key_repeats: false,
+            toggles: AHashMap::with_hasher(hasher.clone()),
+            recent_presses: VecDeque::with_capacity(SEQUENCE_BUFFER_SIZE),
+            scroll: Default::default(),
+            #[cfg(feature = "gamepad")]
+            axes: AHashMap::with_hasher(hasher.clone()),
+            #[cfg(feature = "gamepad")]
+            axis_deadzone: 0.0,
+            recent_releases: AHashMap::with_hasher(hasher),
         }
     }
 }
 
-impl InputData {
+impl<S: BuildHasher> InputData<S> {
     pub fn just_pressed<I: Into<GenericInput>>(&self, input: I) -> bool {
         self.just_pressed.contains(&input.into())
     }
@@ -121,8 +377,8 @@ impl InputData {
         inputs.into_iter().all(|input| self.just_pressed(input))
     }
 
-    pub fn just_pressed_iter(&self) -> impl ExactSizeIterator<Item = GenericInput> {
-        self.just_pressed.clone().into_iter()
+    pub fn just_pressed_iter(&self) -> impl ExactSizeIterator<Item = GenericInput> + '_ {
+        self.just_pressed.iter().copied()
     }
 
     pub fn just_released<I: Into<GenericInput>>(&self, input: I) -> bool {
@@ -143,15 +399,37 @@ impl InputData {
         inputs.into_iter().all(|input| self.just_released(input))
     }
 
-    pub fn just_released_iter(&self) -> impl ExactSizeIterator<Item = GenericInput> {
-        self.just_released.clone().into_iter()
+    pub fn just_released_iter(&self) -> impl ExactSizeIterator<Item = GenericInput> + '_ {
+        self.just_released.iter().copied()
     }
 
     /// Registers the given input as pressed
+    ///
+    /// A repeated press of an input that is already pressed never registers it as just pressed again
+    /// and does not restart the press duration, which is always measured from the first press.
+    /// It only updates [InputData::last_pressed_for], unless [InputData::set_ignore_key_repeats] is enabled.
     pub fn press<I: Into<GenericInput>>(&mut self, input: I) {
         let value = input.into();
-        if self.pressed.insert(value, Instant::now()).is_none() {
+        if self.ignore_key_repeats && self.pressed.contains_key(&value) {
+            return;
+        }
+
+        let now = Instant::now();
+        self.last_pressed.insert(value, now);
+
+        if let Entry::Vacant(entry) = self.pressed.entry(value) {
+            entry.insert(now);
             self.just_pressed.insert(value);
+            self.recent_releases.remove(&value);
+
+            if let Some(toggle) = self.toggles.get_mut(&value) {
+                *toggle = !*toggle;
+            }
+
+            if self.recent_presses.len() == SEQUENCE_BUFFER_SIZE {
+                self.recent_presses.pop_front();
+            }
+            self.recent_presses.push_back((value, now));
         }
     }
 
@@ -167,18 +445,316 @@ impl InputData {
         inputs.into_iter().any(|input| self.pressed(input))
     }
 
+    /// Returns true if the key at the given position on the keyboard is pressed, see [PhysicalKey]
+    pub fn physical_pressed<K: Into<PhysicalKey>>(&self, key: K) -> bool {
+        self.pressed(key.into())
+    }
+
+    /// Returns true if the key at the given position on the keyboard was just pressed, see [PhysicalKey]
+    pub fn physical_just_pressed<K: Into<PhysicalKey>>(&self, key: K) -> bool {
+        self.just_pressed(key.into())
+    }
+
+    /// Returns true if the key at the given position on the keyboard was just released, see [PhysicalKey]
+    pub fn physical_just_released<K: Into<PhysicalKey>>(&self, key: K) -> bool {
+        self.just_released(key.into())
+    }
+
+    /// Returns the pressed keys by their position on the keyboard, see [PhysicalKey]
+    pub fn physical_pressed_iter(&self) -> impl Iterator<Item = PhysicalKey> + '_ {
+        self.pressed_iter()
+            .filter_map(|input| input.try_into().ok())
+    }
+
+    /// Returns how long the given input has been pressed, measured from its first press,
+    /// or `None` if it is not pressed
     pub fn pressed_for<I: Into<GenericInput>>(&self, input: I) -> Option<Duration> {
         self.pressed.get(&input.into()).map(|i| i.elapsed())
     }
 
-    pub fn pressed_iter(&self) -> impl ExactSizeIterator<Item = GenericInput> {
-        self.pressed.clone().into_keys()
+    /// Returns the time since the latest press of the given input, including the repeated presses
+    /// some platforms send while a key is held, or `None` if it is not pressed
+    ///
+    /// Same as [InputData::pressed_for] if [InputData::set_ignore_key_repeats] is enabled.
+    pub fn last_pressed_for<I: Into<GenericInput>>(&self, input: I) -> Option<Duration> {
+        self.last_pressed.get(&input.into()).map(|i| i.elapsed())
+    }
+
+    /// Returns how long ago the given input was released, or `None` if it is pressed or its release is not known.
+    ///
+    /// Only the [RELEASE_BUFFER_SIZE] most recently released inputs are remembered.
+    pub fn released_for<I: Into<GenericInput>>(&self, input: I) -> Option<Duration> {
+        self.recent_releases
+            .get(&input.into())
+            .map(|(instant, _)| instant.elapsed())
+    }
+
+    /// Returns how long the given input has been pressed relative to `ramp_time`, between 0.0 and 1.0,
+    /// e.g. for using a key as an accelerator.
+    ///
+    /// The ramp starts at the first press, so repeated key presses do not restart it.
+    /// Returns 0.0 if the input is not pressed.
+    pub fn analog_ramp<I: Into<GenericInput>>(&self, input: I, ramp_time: Duration) -> f32 {
+        self.pressed_for(input)
+            .map_or(0.0, |pressed_for| ramp(pressed_for, ramp_time))
+    }
+
+    /// Same as [InputData::analog_ramp], but after the input is released
+    /// the value decreases to 0.0 at a rate of 1.0 per `decay_time`, starting from the value at release.
+    ///
+    /// A new press starts the ramp from 0.0 again.
+    /// Only the [RELEASE_BUFFER_SIZE] most recently released inputs decay, any others are 0.0.
+    pub fn analog_ramp_with_decay<I: Into<GenericInput>>(
+        &self,
+        input: I,
+        ramp_time: Duration,
+        decay_time: Duration,
+    ) -> f32 {
+        let input = input.into();
+        if self.pressed(input) {
+            return self.analog_ramp(input, ramp_time);
+        }
+
+        self.recent_releases
+            .get(&input)
+            .map_or(0.0, |&(released_at, pressed_for)| {
+                (ramp(pressed_for, ramp_time) - ramp(released_at.elapsed(), decay_time)).max(0.0)
+            })
+    }
+
+    /// Returns true if the given input was pressed within the given duration, e.g. for jump buffering.
+    ///
+    /// Only the [SEQUENCE_BUFFER_SIZE] most recent presses are taken into account.
+    pub fn pressed_within<I: Into<GenericInput>>(&self, input: I, duration: Duration) -> bool {
+        let input = input.into();
+        self.recent_presses
+            .iter()
+            .rev()
+            .take_while(|(_, instant)| instant.elapsed() <= duration)
+            .any(|&(pressed, _)| pressed == input)
+    }
+
+    /// Returns how long the given input-modifier combination has been pressed,
+    /// or `None` if not all inputs are pressed or the modifiers are not held.
+    ///
+    /// This is the shortest duration among the inputs, as the combination is only complete
+    /// since its most recently pressed input. Like [InputData::pressed_for], the duration of every input
+    /// is measured from its first press. Returns `None` if `inputs` is empty.
+    pub fn pressed_combination_for<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> Option<Duration> {
+        if !self.modifiers.contains(modifiers) {
+            return None;
+        }
+
+        let mut shortest: Option<Duration> = None;
+        for input in inputs {
+            let duration = self.pressed_for(input)?;
+            shortest = Some(shortest.map_or(duration, |shortest| shortest.min(duration)));
+        }
+        shortest
+    }
+
+    /// Sets how long an input has to be pressed before it counts as held
+    ///
+    /// Defaults to zero, which makes [InputData::held] equivalent to [InputData::pressed].
+    pub fn set_hold_threshold(&mut self, threshold: Duration) {
+        self.hold_threshold = threshold;
+    }
+
+    pub fn hold_threshold(&self) -> Duration {
+        self.hold_threshold
+    }
+
+    /// Sets whether repeated presses of an input that is already pressed are ignored
+    ///
+    /// Holding a key makes some platforms send repeated key presses.
+    /// These never register the key as just pressed again and never restart [InputData::pressed_for],
+    /// but by default they update [InputData::last_pressed_for]. Enabling this ignores them completely.
+    pub fn set_ignore_key_repeats(&mut self, ignore: bool) {
+        self.ignore_key_repeats = ignore;
+    }
+
+    pub fn ignore_key_repeats(&self) -> bool {
+        self.ignore_key_repeats
+    }
+
+    /// Returns true if the given input has been pressed for at least the hold threshold, measured from its first press
+    pub fn held<I: Into<GenericInput>>(&self, input: I) -> bool {
+        self.pressed_for(input)
+            .is_some_and(|duration| duration >= self.hold_threshold)
+    }
+
+    /// Returns true if all of the given inputs have been pressed for at least the hold threshold
+    /// and the given modifiers are held
+    pub fn held_combination<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> bool {
+        inputs.into_iter().all(|input| self.held(input)) && self.modifiers.contains(modifiers)
+    }
+
+    pub fn pressed_iter(&self) -> impl ExactSizeIterator<Item = GenericInput> + '_ {
+        self.pressed.keys().copied()
+    }
+
+    /// Tracks the given input as a toggle with the given initial state,
+    /// which flips every time the input is pressed and persists across steps.
+    pub fn register_toggle<I: Into<GenericInput>>(&mut self, input: I, initial: bool) {
+        self.toggles.insert(input.into(), initial);
+    }
+
+    /// Stops tracking the given input as a toggle, returning its state if it was tracked
+    pub fn unregister_toggle<I: Into<GenericInput>>(&mut self, input: I) -> Option<bool> {
+        self.toggles.remove(&input.into())
+    }
+
+    /// Returns the state of the given toggle, or false if the input is not tracked as a toggle
+    pub fn toggle_state<I: Into<GenericInput>>(&self, input: I) -> bool {
+        self.toggles.get(&input.into()).copied().unwrap_or_default()
+    }
+
+    /// Returns the currently held modifiers
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
     }
 
     pub fn update_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers_changed |= self.modifiers != modifiers;
         self.modifiers = modifiers;
     }
 
+    /// Returns true if the modifiers changed this step
+    pub fn modifiers_changed(&self) -> bool {
+        self.modifiers_changed
+    }
+
+    /// Returns the inputs that were pressed and released this step and whether the modifiers changed
+    pub fn changes(&self) -> InputChanges {
+        InputChanges {
+            newly_pressed: self.just_pressed.iter().copied().collect(),
+            newly_released: self.just_released.iter().copied().collect(),
+            modifiers_changed: self.modifiers_changed,
+        }
+    }
+
+    /// Returns true if any input was pressed or released this step, see [InputData::changes]
+    pub fn changed(&self) -> bool {
+        !self.just_pressed.is_empty() || !self.just_released.is_empty()
+    }
+
+    /// Returns true if a `just_pressed` callback registered for the given combination would activate this step.
+    ///
+    /// Equivalent to [InputData::just_pressed_combination].
+    pub fn would_fire_just_pressed<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> bool {
+        self.just_pressed_combination(inputs, modifiers)
+    }
+
+    /// Returns true if any of the given callbacks would activate this step, without activating them.
+    pub fn would_any_fire<D>(&self, callbacks: &InputCallbacks<D, S>) -> bool {
+        callbacks.pressed.keys().any(|(inputs, modifiers)| {
+            self.pressed_combination_matching(inputs.iter().copied(), *modifiers)
+        }) || callbacks.just_pressed.keys().any(|(inputs, modifiers)| {
+            self.just_pressed_combination_matching(inputs.iter().copied(), *modifiers)
+        }) || callbacks.just_released.keys().any(|(inputs, modifiers)| {
+            self.just_released_combination_matching(inputs.iter().copied(), *modifiers)
+        }) || callbacks
+            .sequences
+            .keys()
+            .any(|(sequence, within)| self.just_completed_sequence(sequence, *within))
+            || callbacks
+                .double_clicks
+                .keys()
+                .any(|(button, within)| self.double_clicked(*button, *within))
+            || callbacks
+                .held
+                .iter()
+                .any(|((input, duration), (_, fired_for))| {
+                    self.held_callback_due(input, *duration, fired_for.get())
+                        .is_some()
+                })
+            || callbacks
+                .repeats
+                .iter()
+                .any(|((input, initial_delay, interval), (_, fired_for))| {
+                    self.repeat_callback_due(input, *initial_delay, *interval, fired_for.get())
+                        .is_some()
+                })
+            || callbacks
+                .just_pressed_filtered
+                .iter()
+                .any(|(predicate, _)| self.just_pressed.iter().any(|input| predicate(*input)))
+            || callbacks
+                .just_released_filtered
+                .iter()
+                .any(|(predicate, _)| self.just_released.iter().any(|input| predicate(*input)))
+            || callbacks
+                .keyboard_input_filtered
+                .iter()
+                .any(|(predicate, _)| {
+                    keyboard_inputs(&self.just_pressed, &self.just_released).any(predicate)
+                })
+            || callbacks
+                .just_pressed_any_with
+                .iter()
+                .any(|(inputs, _)| !inputs.is_disjoint(&self.just_pressed))
+            || callbacks
+                .just_released_any_with
+                .iter()
+                .any(|(inputs, _)| !inputs.is_disjoint(&self.just_released))
+    }
+
+    /// Returns the instant the input was pressed and the number of activations that are due
+    /// if a `repeat` callback that last activated `fired_for` is due.
+    fn repeat_callback_due(
+        &self,
+        input: &GenericInput,
+        initial_delay: Duration,
+        interval: Duration,
+        fired_for: Option<(Instant, u32)>,
+    ) -> Option<(Instant, u32)> {
+        let pressed_at = *self.pressed.get(input)?;
+        let elapsed = pressed_at.elapsed();
+
+        // the press itself, then the activation after the initial delay, then one per interval
+        let due = match elapsed.checked_sub(initial_delay) {
+            None => 1,
+            Some(repeating) => {
+                let intervals = repeating.as_nanos() / interval.as_nanos().max(1);
+                u32::try_from(intervals)
+                    .unwrap_or(u32::MAX)
+                    .saturating_add(2)
+            }
+        };
+        let fired = match fired_for {
+            Some((instant, fired)) if instant == pressed_at => fired,
+            _ => 0,
+        };
+
+        (due > fired).then_some((pressed_at, due))
+    }
+
+    /// Returns the instant the input was pressed if a `held_for` callback that last activated
+    /// for the press at `fired_for` is due.
+    fn held_callback_due(
+        &self,
+        input: &GenericInput,
+        duration: Duration,
+        fired_for: Option<Instant>,
+    ) -> Option<Instant> {
+        self.pressed
+            .get(input)
+            .copied()
+            .filter(|&pressed_at| pressed_at.elapsed() >= duration && fired_for != Some(pressed_at))
+    }
+
     pub fn just_pressed_combination<I: Into<GenericInput>>(
         &self,
         inputs: impl IntoIterator<Item = I>,
@@ -203,6 +779,221 @@ impl InputData {
         self.just_released_all(inputs) && self.modifiers.contains(modifiers)
     }
 
+    /// Same as [InputData::just_pressed_combination], but requires exactly the given modifiers to be held
+    pub fn just_pressed_combination_exact<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> bool {
+        self.just_pressed_all(inputs) && self.modifiers == modifiers
+    }
+
+    /// Same as [InputData::pressed_combination], but requires exactly the given modifiers to be held
+    pub fn pressed_combination_exact<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> bool {
+        self.pressed_all(inputs) && self.modifiers == modifiers
+    }
+
+    /// Same as [InputData::just_released_combination], but requires exactly the given modifiers to be held
+    pub fn just_released_combination_exact<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: Modifiers,
+    ) -> bool {
+        self.just_released_all(inputs) && self.modifiers == modifiers
+    }
+
+    /// Returns true if all of the given inputs are pressed and the held modifiers match `modifiers`
+    ///
+    /// `modifiers` can be [Modifiers] or a [ModifierMatch], which can forbid modifiers from being held.
+    pub fn pressed_combination_matching<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+    ) -> bool {
+        self.pressed_all(inputs) && modifiers.into().matches(self.modifiers)
+    }
+
+    /// Returns true if all of the given inputs were just pressed and the held modifiers match `modifiers`
+    ///
+    /// `modifiers` can be [Modifiers] or a [ModifierMatch], which can forbid modifiers from being held.
+    pub fn just_pressed_combination_matching<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+    ) -> bool {
+        self.just_pressed_all(inputs) && modifiers.into().matches(self.modifiers)
+    }
+
+    /// Returns true if all of the given inputs were just released and the held modifiers match `modifiers`
+    ///
+    /// `modifiers` can be [Modifiers] or a [ModifierMatch], which can forbid modifiers from being held.
+    pub fn just_released_combination_matching<I: Into<GenericInput>>(
+        &self,
+        inputs: impl IntoIterator<Item = I>,
+        modifiers: impl Into<ModifierMatch>,
+    ) -> bool {
+        self.just_released_all(inputs) && modifiers.into().matches(self.modifiers)
+    }
+
+    /// Returns true if the last input of `sequence` was just pressed
+    /// and all inputs of `sequence` were pressed in order within the given duration.
+    ///
+    /// Other inputs pressed in between do not break the sequence.
+    pub fn just_completed_sequence(&self, sequence: &[GenericInput], within: Duration) -> bool {
+        let Some((last, rest)) = sequence.split_last() else {
+            return false;
+        };
+
+        if !self.just_pressed.contains(last) {
+            return false;
+        }
+
+        let mut presses = self.recent_presses.iter().rev();
+        let Some(&(_, end)) = presses.find(|(input, _)| input == last) else {
+            return false;
+        };
+
+        let mut start = end;
+        for expected in rest.iter().rev() {
+            match presses.find(|(input, _)| input == expected) {
+                Some(&(_, instant)) => start = instant,
+                None => return false,
+            }
+        }
+
+        end.duration_since(start) <= within
+    }
+
+    /// Returns true if the given button was just pressed within `within` of its previous press.
+    ///
+    /// Consecutive clicks are paired up, so a triple click counts as a single double click
+    /// and a fourth click counts as the next one.
+    pub fn double_clicked(&self, button: MouseButton, within: Duration) -> bool {
+        if !self.just_pressed(button) {
+            return false;
+        }
+
+        let mut presses = self
+            .recent_presses
+            .iter()
+            .rev()
+            .filter(|(input, _)| *input == GenericInput::MouseButton(button))
+            .map(|(_, instant)| *instant);
+
+        let Some(mut later) = presses.next() else {
+            return false;
+        };
+
+        let mut clicks = 1;
+        for earlier in presses {
+            if later.duration_since(earlier) > within {
+                break;
+            }
+            clicks += 1;
+            later = earlier;
+        }
+
+        clicks % 2 == 0
+    }
+
+    /// Packs the pressed state of the inputs in `layout` into a bitmask,
+    /// where bit `i` is set if `layout[i]` is pressed.
+    ///
+    /// The modifiers are stored in the 4 bits after [INPUT_FRAME_MAX_INPUTS],
+    /// so the frame only depends on the layout and the pressed state.
+    ///
+    /// The frame is a `u128` instead of a `u64`, as a full layout of 64 inputs leaves no bits for the modifiers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layout` holds more than [INPUT_FRAME_MAX_INPUTS] inputs.
+    pub fn to_input_frame(&self, layout: &[GenericInput]) -> u128 {
+        assert_input_frame_layout(layout);
+
+        let inputs = layout
+            .iter()
+            .enumerate()
+            .filter(|(_, input)| self.pressed.contains_key(input))
+            .fold(0, |frame, (i, _)| frame | 1 << i);
+
+        INPUT_FRAME_MODIFIERS
+            .iter()
+            .enumerate()
+            .filter(|(_, modifier)| self.modifiers.contains(**modifier))
+            .fold(inputs, |frame, (i, _)| {
+                frame | 1 << (INPUT_FRAME_MAX_INPUTS + i)
+            })
+    }
+
+    /// Presses and releases the inputs in `layout` so that their pressed state matches `frame`
+    /// and sets the modifiers stored in it through [InputData::update_modifiers].
+    ///
+    /// The inverse of [InputData::to_input_frame]. Inputs not in `layout` are left untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layout` holds more than [INPUT_FRAME_MAX_INPUTS] inputs.
+    pub fn apply_input_frame(&mut self, layout: &[GenericInput], frame: u128) {
+        assert_input_frame_layout(layout);
+
+        for (i, &input) in layout.iter().enumerate() {
+            let pressed = frame & 1 << i != 0;
+
+            if pressed && !self.pressed.contains_key(&input) {
+                self.press(input);
+            } else if !pressed && self.pressed.contains_key(&input) {
+                self.release(input);
+            }
+        }
+
+        let modifiers = INPUT_FRAME_MODIFIERS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| frame & 1 << (INPUT_FRAME_MAX_INPUTS + i) != 0)
+            .fold(Modifiers::empty(), |modifiers, (_, &modifier)| {
+                modifiers | modifier
+            });
+        self.update_modifiers(modifiers);
+    }
+
+    /// Returns the pressed inputs and held modifiers, without any timing information
+    pub fn snapshot(&self) -> InputSnapshot {
+        let mut pressed: Vec<GenericInput> = self.pressed.keys().copied().collect();
+        pressed.sort_unstable();
+
+        InputSnapshot {
+            pressed,
+            modifiers: self.modifiers,
+        }
+    }
+
+    /// Presses and releases inputs so that the pressed inputs match `snapshot` and sets its modifiers.
+    ///
+    /// The inverse of [InputData::snapshot]. Inputs that change state are registered as
+    /// just pressed or just released, like inputs from events.
+    pub fn apply_snapshot(&mut self, snapshot: &InputSnapshot) {
+        let released: Vec<GenericInput> = self
+            .pressed
+            .keys()
+            .filter(|input| !snapshot.pressed.contains(input))
+            .copied()
+            .collect();
+
+        released.into_iter().for_each(|input| self.release(input));
+
+        for &input in &snapshot.pressed {
+            if !self.pressed.contains_key(&input) {
+                self.press(input);
+            }
+        }
+
+        self.update_modifiers(snapshot.modifiers);
+    }
+
     /// Returns true if any alt key is pressed
     pub fn pressed_alt(&self) -> bool {
         self.modifiers.alt()
@@ -224,24 +1015,109 @@ impl InputData {
     }
 
     /// Registers the given input as released
+    ///
+    /// An input that is pressed and released before the next [InputData::clear]
+    /// counts as both just pressed and just released, so short taps are not lost.
     pub fn release<I: Into<GenericInput>>(&mut self, input: I) {
+        let value = input.into();
+        let pressed_for = self
+            .pressed
+            .remove(&value)
+            .map(|instant| instant.elapsed())
+            .unwrap_or_default();
+        self.last_pressed.remove(&value);
+        self.just_released.insert(value);
+
+        if self.recent_releases.len() == RELEASE_BUFFER_SIZE
+            && !self.recent_releases.contains_key(&value)
+        {
+            if let Some(oldest) = self
+                .recent_releases
+                .iter()
+                .min_by_key(|(_, &(instant, _))| instant)
+                .map(|(&input, _)| input)
+            {
+                self.recent_releases.remove(&oldest);
+            }
+        }
+        self.recent_releases
+            .insert(value, (Instant::now(), pressed_for));
+    }
+
+    /// Removes the given input from the pressed, just pressed and just released inputs
+    /// without registering it as released.
+    ///
+    /// Useful when the window loses focus while an input is held,
+    /// as the release would otherwise be reported as if the user released it.
+    pub fn forget<I: Into<GenericInput>>(&mut self, input: I) {
         let value = input.into();
         self.pressed.remove(&value);
+        self.last_pressed.remove(&value);
         self.just_pressed.remove(&value);
-        self.just_released.insert(value);
+        self.just_released.remove(&value);
+    }
+
+    /// Forgets all pressed inputs, see [InputData::forget]
+    pub fn forget_pressed(&mut self) {
+        self.pressed.clear();
+        self.last_pressed.clear();
+        self.just_pressed.clear();
     }
 
-    /// Clears the `just_pressed` and `just_released` fields
+    /// Clears the `just_pressed` and `just_released` fields and the accumulated scroll
+    ///
+    /// Recent presses are kept so sequences can span multiple steps.
     pub fn clear(&mut self) {
         self.just_pressed.clear();
         self.just_released.clear();
+        self.modifiers_changed = false;
+        self.scroll = Default::default();
+    }
+
+    /// Adds the `just_pressed` and `just_released` inputs of `other` to those of `self`.
+    ///
+    /// Presses and releases already accumulate across events until [InputData::clear] is called,
+    /// including inputs that were released again, so this is only needed to combine separately collected data.
+    pub fn merge_just_states(&mut self, other: &Self) {
+        self.just_pressed.extend(other.just_pressed.iter().copied());
+        self.just_released
+            .extend(other.just_released.iter().copied());
+    }
+
+    /// Adds the inputs and modifiers of `other` to those of `self`.
+    ///
+    /// Inputs pressed in both keep the earlier first press and the later latest press. Scroll deltas are summed.
+    pub fn merge(&mut self, other: &Self) {
+        for (&input, &pressed_at) in &other.pressed {
+            self.pressed
+                .entry(input)
+                .and_modify(|instant| *instant = (*instant).min(pressed_at))
+                .or_insert(pressed_at);
+        }
+        for (&input, &pressed_at) in &other.last_pressed {
+            self.last_pressed
+                .entry(input)
+                .and_modify(|instant| *instant = (*instant).max(pressed_at))
+                .or_insert(pressed_at);
+        }
+        self.merge_just_states(other);
+        self.modifiers |= other.modifiers;
+        self.modifiers_changed |= other.modifiers_changed;
+        self.scroll.0 += other.scroll.0;
+        self.scroll.1 += other.scroll.1;
     }
 
     /// Resets all fields
     pub fn reset(&mut self) {
         self.pressed.clear();
+        self.last_pressed.clear();
         self.just_pressed.clear();
         self.just_released.clear();
+        self.recent_presses.clear();
+        self.recent_releases.clear();
+        self.scroll = Default::default();
+        #[cfg(feature = "gamepad")]
+        self.axes.clear();
     }
 
     pub fn update<I: Into<GenericInput>>(&mut self, value: I, state: ElementState) {
@@ -255,29 +1131,227 @@ impl InputData {
         }
     }
 
-    pub fn key_just_pressed_iter(&self) -> impl Iterator<Item = VirtualKeyCode> {
+    /// Adds the given scroll to the accumulated scroll
+    ///
+    /// Line and pixel deltas are accumulated separately.
+    pub fn update_scroll(&mut self, delta: MouseScrollDelta) {
+        let (lines, pixels) = &mut self.scroll;
+        *lines += delta.try_into().unwrap_or_default();
+        *pixels += delta.try_into().unwrap_or_default();
+    }
+
+    /// Returns the line and pixel deltas scrolled since the last [InputData::clear]
+    pub fn scroll_delta(&self) -> (LineDelta, PixelDelta) {
+        self.scroll
+    }
+
+    #[cfg(feature = "gamepad")]
+    /// Sets the value of the given gamepad axis
+    pub fn update_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.axes.insert(axis, value);
+    }
+
+    #[cfg(feature = "gamepad")]
+    /// Returns the last known value of the given gamepad axis with the deadzone applied,
+    /// or 0.0 if it never changed
+    ///
+    /// See [apply_deadzone](crate::input::axes::apply_deadzone).
+    pub fn axis_value(&self, axis: GamepadAxis) -> f32 {
+        apply_deadzone(self.axis_value_raw(axis).into(), self.axis_deadzone.into()) as f32
+    }
+
+    #[cfg(feature = "gamepad")]
+    /// Returns the last known value of the given gamepad axis as it was received, or 0.0 if it never changed
+    pub fn axis_value_raw(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or_default()
+    }
+
+    #[cfg(feature = "gamepad")]
+    /// Sets the deadzone applied by [InputData::axis_value]
+    ///
+    /// Defaults to zero.
+    pub fn set_axis_deadzone(&mut self, deadzone: f32) {
+        self.axis_deadzone = deadzone;
+    }
+
+    #[cfg(feature = "gamepad")]
+    pub fn axis_deadzone(&self) -> f32 {
+        self.axis_deadzone
+    }
+
+    pub fn key_just_pressed_iter(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
         filter_keyboard_keys(self.just_pressed_iter())
     }
 
-    pub fn button_just_pressed_iter(&self) -> impl Iterator<Item = MouseButton> {
+    pub fn button_just_pressed_iter(&self) -> impl Iterator<Item = MouseButton> + '_ {
         filter_mouse_buttons(self.just_pressed_iter())
     }
 
-    pub fn key_just_released_iter(&self) -> impl Iterator<Item = VirtualKeyCode> {
+    pub fn key_just_released_iter(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
         filter_keyboard_keys(self.just_released_iter())
     }
 
-    pub fn button_just_released_iter(&self) -> impl Iterator<Item = MouseButton> {
+    pub fn button_just_released_iter(&self) -> impl Iterator<Item = MouseButton> + '_ {
         filter_mouse_buttons(self.just_released_iter())
     }
 
-    pub fn key_pressed_iter(&self) -> impl Iterator<Item = VirtualKeyCode> {
+    pub fn key_pressed_iter(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
         filter_keyboard_keys(self.pressed_iter())
     }
 
-    pub fn button_pressed_iter(&self) -> impl Iterator<Item = MouseButton> {
+    pub fn button_pressed_iter(&self) -> impl Iterator<Item = MouseButton> + '_ {
         filter_mouse_buttons(self.pressed_iter())
     }
+
+    pub fn scancode_just_pressed_iter(&self) -> impl Iterator<Item = ScanCode> + '_ {
+        filter_scancodes(self.just_pressed_iter())
+    }
+
+    pub fn scancode_just_released_iter(&self) -> impl Iterator<Item = ScanCode> + '_ {
+        filter_scancodes(self.just_released_iter())
+    }
+
+    pub fn scancode_pressed_iter(&self) -> impl Iterator<Item = ScanCode> + '_ {
+        filter_scancodes(self.pressed_iter())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The pressed inputs and held modifiers at a point in time, see [InputData::snapshot].
+///
+/// Unlike [InputData], it holds no instants, so it can be sent over the network or recorded for replays.
+pub struct InputSnapshot {
+    /// The pressed inputs in sorted order.
+    pub pressed: Vec<GenericInput>,
+    pub modifiers: Modifiers,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// The changes in input state during a step, see [InputData::changes].
+pub struct InputChanges {
+    pub newly_pressed: Vec<GenericInput>,
+    pub newly_released: Vec<GenericInput>,
+    pub modifiers_changed: bool,
+}
+
+impl InputChanges {
+    /// Returns true if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.newly_pressed.is_empty() && self.newly_released.is_empty() && !self.modifiers_changed
+    }
+}
+
+/// Calls every callback once for each of the given inputs its predicate returns true for.
+fn call_filtered_callbacks<D, S>(
+    event_helper: &mut EventHelper<D>,
+    callbacks: &[FilteredCallback<D>],
+    inputs: &AHashSet<GenericInput, S>,
+) {
+    for (predicate, func) in callbacks {
+        for &input in inputs.iter().filter(|input| predicate(**input)) {
+            if event_helper.input_consumed {
+                return;
+            }
+            crate::__invoke_callback!(func, event_helper, input);
+        }
+    }
+}
+
+fn call_keyboard_filtered_callbacks<D, S>(
+    event_helper: &mut EventHelper<D>,
+    callbacks: &[KeyboardFilteredCallback<D>],
+    just_pressed: &AHashSet<GenericInput, S>,
+    just_released: &AHashSet<GenericInput, S>,
+) {
+    for (predicate, func) in callbacks {
+        for input in keyboard_inputs(just_pressed, just_released).filter(|input| predicate(*input))
+        {
+            if event_helper.input_consumed {
+                return;
+            }
+            crate::__invoke_callback!(func, event_helper, input);
+        }
+    }
+}
+
+/// Returns the just pressed and just released keys together with their state.
+fn keyboard_inputs<'a, S>(
+    just_pressed: &'a AHashSet<GenericInput, S>,
+    just_released: &'a AHashSet<GenericInput, S>,
+) -> impl Iterator<Item = (KeyCode, ElementState)> + 'a {
+    let pressed =
+        filter_keyboard_keys(just_pressed.iter().copied()).map(|key| (key, ElementState::Pressed));
+    let released = filter_keyboard_keys(just_released.iter().copied())
+        .map(|key| (key, ElementState::Released));
+
+    pressed.chain(released)
+}
+
+/// Returns `elapsed` relative to `duration`, between 0.0 and 1.0.
+fn ramp(elapsed: Duration, duration: Duration) -> f32 {
+    if duration.is_zero() {
+        return 1.0;
+    }
+    (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+}
+
+fn call_any_of_callbacks<D, S: BuildHasher>(
+    event_helper: &mut EventHelper<D>,
+    callbacks: &[AnyOfCallback<D, S>],
+    inputs: &AHashSet<GenericInput, S>,
+) {
+    for (any_of, func) in callbacks {
+        for &input in any_of.intersection(inputs) {
+            if event_helper.input_consumed {
+                return;
+            }
+            crate::__invoke_callback!(func, event_helper, input);
+        }
+    }
+}
+
+/// Calls the matching callbacks in order of descending priority until the input is consumed.
+///
+/// Callbacks with the same priority are ordered by descending number of inputs and modifiers,
+/// so more specific combinations activate first, and then by the order they were registered in.
+fn call_combination_callbacks<D, S>(
+    event_helper: &mut EventHelper<D>,
+    callbacks: &AHashMap<(Vec<GenericInput>, ModifierMatch), PrioritizedCallback<D>, S>,
+    matches: impl Fn(&[GenericInput], ModifierMatch) -> bool,
+) {
+    let mut matched: Vec<_> = callbacks
+        .iter()
+        .filter(|((inputs, modifiers), _)| matches(inputs, *modifiers))
+        .collect();
+
+    matched.sort_by_key(|((inputs, modifiers), (_, priority, order))| {
+        (
+            Reverse((
+                *priority,
+                inputs.len(),
+                modifiers.required.bits().count_ones(),
+                modifiers.forbidden.bits().count_ones(),
+            )),
+            *order,
+        )
+    });
+
+    for (_, (func, ..)) in matched {
+        if event_helper.input_consumed {
+            break;
+        }
+        crate::__invoke_callback!(func, event_helper);
+    }
+}
+
+fn assert_input_frame_layout(layout: &[GenericInput]) {
+    assert!(
+        layout.len() <= INPUT_FRAME_MAX_INPUTS,
+        "input frame layouts can hold at most {} inputs, got {}",
+        INPUT_FRAME_MAX_INPUTS,
+        layout.len()
+    );
 }
 
 pub fn filter_keyboard_keys<I>(iter: I) -> impl Iterator<Item = KeyCode>
@@ -293,3 +1367,390 @@ where
 {
     iter.filter_map(|input| input.try_into().ok())
 }
+
+pub fn filter_scancodes<I>(iter: I) -> impl Iterator<Item = ScanCode>
+where
+    I: Iterator<Item = GenericInput>,
+{
+    iter.filter_map(|input| input.try_into().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    // the timing tests leave margins of at least 50 ms, so they also pass on loaded machines
+    use std::thread::sleep;
+
+    use winit::event::VirtualKeyCode;
+
+    use super::*;
+    use crate::{testing, Callbacks};
+
+    #[test]
+    fn repeated_press_does_not_reset_hold() {
+        let mut data = InputData::new();
+        data.set_hold_threshold(Duration::from_millis(200));
+
+        data.press(VirtualKeyCode::Space);
+        sleep(Duration::from_millis(250));
+        data.press(VirtualKeyCode::Space);
+
+        assert!(data.held(VirtualKeyCode::Space));
+        assert!(data.pressed_for(VirtualKeyCode::Space).unwrap() >= Duration::from_millis(200));
+        assert!(data.last_pressed_for(VirtualKeyCode::Space).unwrap() < Duration::from_millis(200));
+    }
+
+    #[test]
+    fn held_for_activates_once_across_repeated_presses() {
+        let mut eh = EventHelper::new(0);
+        let mut callbacks = Callbacks::<i32>::empty();
+        callbacks
+            .window
+            .inputs
+            .held_for(VirtualKeyCode::Space, Duration::from_millis(200), |eh| {
+                **eh += 1
+            });
+
+        testing::feed_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::Space,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+        sleep(Duration::from_millis(250));
+        testing::feed_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::Space,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+    }
+
+    #[test]
+    fn repeat_timing_ignores_repeated_presses() {
+        let mut eh = EventHelper::new(0);
+        let mut callbacks = Callbacks::<i32>::empty();
+        callbacks.window.inputs.repeat(
+            VirtualKeyCode::Down,
+            Duration::from_millis(300),
+            Duration::from_secs(1),
+            |eh| **eh += 1,
+        );
+
+        testing::feed_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::Down,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+
+        sleep(Duration::from_millis(200));
+        testing::feed_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::Down,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+
+        sleep(Duration::from_millis(150));
+        testing::step(&mut eh, &callbacks);
+        assert_eq!(*eh, 2);
+    }
+
+    #[test]
+    fn analog_ramp_keeps_rising_across_repeated_presses() {
+        let mut data = InputData::new();
+        let ramp_time = Duration::from_secs(1);
+
+        data.press(VirtualKeyCode::W);
+        sleep(Duration::from_millis(200));
+        let before = data.analog_ramp(VirtualKeyCode::W, ramp_time);
+        data.press(VirtualKeyCode::W);
+        let after = data.analog_ramp(VirtualKeyCode::W, ramp_time);
+
+        assert!(before >= 0.2);
+        assert!(after >= before);
+    }
+
+    #[test]
+    fn pressed_combination_for_ignores_repeated_presses() {
+        let mut data = InputData::new();
+        data.update_modifiers(Modifiers::CTRL);
+        data.press(VirtualKeyCode::LControl);
+        data.press(VirtualKeyCode::S);
+        sleep(Duration::from_millis(200));
+        data.press(VirtualKeyCode::S);
+
+        let duration = data
+            .pressed_combination_for(
+                [VirtualKeyCode::LControl, VirtualKeyCode::S],
+                Modifiers::CTRL,
+            )
+            .unwrap();
+        assert!(duration >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn input_frame_round_trips_full_layout() {
+        let layout: Vec<GenericInput> = (0..INPUT_FRAME_MAX_INPUTS as u32)
+            .map(GenericInput::ScanCode)
+            .collect();
+
+        let mut data = InputData::new();
+        for &input in layout.iter().step_by(3).chain(layout.last()) {
+            data.press(input);
+        }
+        data.update_modifiers(Modifiers::CTRL | Modifiers::LOGO);
+
+        let frame = data.to_input_frame(&layout);
+        assert_ne!(frame & 1 << (INPUT_FRAME_MAX_INPUTS - 1), 0);
+
+        let mut applied = InputData::new();
+        applied.apply_input_frame(&layout, frame);
+        assert_eq!(applied.snapshot(), data.snapshot());
+        assert_eq!(applied.to_input_frame(&layout), frame);
+        assert!(applied.modifiers_changed());
+        assert!(applied.changes().modifiers_changed);
+
+        // restoring the same modifiers again is not a change
+        applied.clear();
+        applied.apply_input_frame(&layout, frame);
+        assert!(!applied.modifiers_changed());
+    }
+
+    #[test]
+    fn presses_accumulate_until_clear() {
+        let mut data = InputData::new();
+
+        data.update(VirtualKeyCode::A, ElementState::Pressed);
+        data.update(VirtualKeyCode::A, ElementState::Released);
+        data.update(VirtualKeyCode::B, ElementState::Pressed);
+
+        assert!(data.just_pressed_all([VirtualKeyCode::A, VirtualKeyCode::B]));
+        assert!(data.just_released(VirtualKeyCode::A));
+        assert!(!data.pressed(VirtualKeyCode::A));
+
+        data.clear();
+        assert_eq!(data.just_pressed_iter().len(), 0);
+    }
+
+    #[test]
+    fn equal_priorities_activate_in_registration_order() {
+        let keys = [VirtualKeyCode::A, VirtualKeyCode::B, VirtualKeyCode::C];
+        let pushers: [fn(&mut EventHelper<Vec<usize>>); 3] =
+            [|eh| eh.push(0), |eh| eh.push(1), |eh| eh.push(2)];
+        let orders = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        for order in orders {
+            let mut eh = EventHelper::new(Vec::new());
+            let mut callbacks = Callbacks::<Vec<usize>>::empty();
+            for i in order {
+                callbacks.window.inputs.just_pressed(keys[i], pushers[i]);
+            }
+
+            for key in keys {
+                testing::feed_key(&mut eh, &callbacks, key, ElementState::Pressed);
+            }
+            testing::step(&mut eh, &callbacks);
+
+            assert_eq!(*eh, order.to_vec());
+        }
+    }
+
+    #[test]
+    fn consumed_input_stops_other_callbacks() {
+        let mut eh = EventHelper::new(0);
+        let mut callbacks = Callbacks::<i32>::empty();
+        callbacks
+            .window
+            .inputs
+            .just_pressed_combination_with_priority(
+                [VirtualKeyCode::A],
+                Modifiers::empty(),
+                1,
+                |eh| eh.consume_input(),
+            );
+        callbacks
+            .window
+            .inputs
+            .sequence([VirtualKeyCode::A], Duration::from_secs(1), |eh| **eh += 1);
+        callbacks
+            .window
+            .inputs
+            .held_for(VirtualKeyCode::A, Duration::ZERO, |eh| **eh += 1);
+        callbacks
+            .device
+            .inputs
+            .just_pressed(VirtualKeyCode::A, |eh| **eh += 10);
+
+        testing::feed_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::A,
+            ElementState::Pressed,
+        );
+        testing::feed_device_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::A,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+
+        // only the device inputs, which are a separate pass, are not consumed
+        assert_eq!(*eh, 10);
+    }
+
+    #[test]
+    fn input_data_with_custom_hasher() {
+        use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+        type Sip = BuildHasherDefault<DefaultHasher>;
+
+        let mut callbacks = InputCallbacks::<i32, Sip>::default();
+        callbacks.just_pressed(VirtualKeyCode::A, |eh| **eh += 1);
+        callbacks.just_pressed_any_with([VirtualKeyCode::A, VirtualKeyCode::B], |eh, _| **eh += 10);
+
+        let mut with_id = InputDataWithId::<u8, Sip>::default();
+        with_id.get_mut(0).press(VirtualKeyCode::A);
+        with_id.get_mut(1).press(VirtualKeyCode::B);
+        let merged = with_id.merged();
+        assert!(merged.just_pressed_all([VirtualKeyCode::A, VirtualKeyCode::B]));
+
+        let mut eh = EventHelper::new(0);
+        with_id[0].call_callbacks(&mut eh, &callbacks);
+        assert_eq!(*eh, 11);
+    }
+
+    #[test]
+    fn would_any_fire_checks_without_activating() {
+        let mut callbacks = InputCallbacks::<i32>::default();
+        callbacks.just_pressed(VirtualKeyCode::Space, |eh| **eh += 1);
+        callbacks.pressed_combination([VirtualKeyCode::S], Modifiers::CTRL, |eh| **eh += 1);
+
+        let mut data = InputData::new();
+        assert!(!data.would_any_fire(&callbacks));
+
+        data.press(VirtualKeyCode::A);
+        assert!(!data.would_any_fire(&callbacks));
+
+        data.press(VirtualKeyCode::Space);
+        assert!(data.would_any_fire(&callbacks));
+
+        // still pressed, but not just pressed anymore
+        data.clear();
+        assert!(!data.would_any_fire(&callbacks));
+
+        data.update_modifiers(Modifiers::CTRL);
+        data.press(VirtualKeyCode::S);
+        assert!(data.would_any_fire(&callbacks));
+    }
+
+    #[test]
+    fn toggle_flips_on_every_press_but_not_on_repeats() {
+        let mut data = InputData::new();
+        data.register_toggle(VirtualKeyCode::Capital, false);
+
+        data.press(VirtualKeyCode::Capital);
+        data.press(VirtualKeyCode::Capital);
+        assert!(data.toggle_state(VirtualKeyCode::Capital));
+        data.release(VirtualKeyCode::Capital);
+        assert!(data.toggle_state(VirtualKeyCode::Capital));
+
+        data.press(VirtualKeyCode::Capital);
+        data.release(VirtualKeyCode::Capital);
+        assert!(!data.toggle_state(VirtualKeyCode::Capital));
+
+        assert_eq!(data.unregister_toggle(VirtualKeyCode::Capital), Some(false));
+        data.press(VirtualKeyCode::Capital);
+        assert!(!data.toggle_state(VirtualKeyCode::Capital));
+    }
+
+    #[test]
+    fn key_repeats_are_not_just_pressed_again() {
+        for ignore in [false, true] {
+            let mut data = InputData::new();
+            data.set_ignore_key_repeats(ignore);
+
+            data.press(VirtualKeyCode::A);
+            assert!(data.just_pressed(VirtualKeyCode::A));
+            data.clear();
+            sleep(Duration::from_millis(200));
+
+            data.press(VirtualKeyCode::A);
+            assert!(!data.just_pressed(VirtualKeyCode::A));
+            assert!(data.pressed(VirtualKeyCode::A));
+            assert_eq!(
+                data.last_pressed_for(VirtualKeyCode::A).unwrap() >= Duration::from_millis(200),
+                ignore
+            );
+        }
+    }
+
+    #[test]
+    fn hold_threshold_applies_to_device_inputs() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+        eh.data
+            .device
+            .inputs
+            .set_hold_threshold(Duration::from_millis(200));
+
+        testing::feed_device_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::A,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+        assert!(eh.data.device.inputs.pressed(VirtualKeyCode::A));
+        assert!(!eh.data.device.inputs.held(VirtualKeyCode::A));
+
+        sleep(Duration::from_millis(250));
+        testing::step(&mut eh, &callbacks);
+        assert!(eh.data.device.inputs.held(VirtualKeyCode::A));
+        assert_eq!(
+            eh.data.device.inputs.hold_threshold(),
+            Duration::from_millis(200)
+        );
+    }
+
+    #[test]
+    fn input_data_with_id_dispatches_by_synthetic_key() {
+        let mut eh = EventHelper::new(0);
+        let mut callbacks = DefaultAHashMap::<u32, InputCallbacks<i32>>::default();
+        callbacks
+            .get_mut(1)
+            .just_pressed(VirtualKeyCode::A, |eh| **eh += 1);
+        callbacks
+            .get_mut(2)
+            .just_pressed(VirtualKeyCode::A, |eh| **eh += 10);
+
+        let mut data = InputDataWithId::<u32>::default();
+        data.get_mut(1).press(VirtualKeyCode::A);
+        data.get_mut(2).press(VirtualKeyCode::B);
+        data.get_mut(3).press(VirtualKeyCode::A);
+
+        data.call_callbacks(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+        assert!(data
+            .merged()
+            .pressed_all([VirtualKeyCode::A, VirtualKeyCode::B]));
+    }
+}
@@ -6,9 +6,17 @@
 //! Callbacks are collected in [InputCallbacks],
 //! which can be accessed via the `callbacks` field on the [EventHelper](crate::EventHelper) struct.
 
+pub mod axes;
 pub mod callbacks;
 pub mod data;
+pub mod players;
 
+pub use axes::AxisValues;
+pub use callbacks::BindingHandle;
+pub use callbacks::BindingKind;
 pub use callbacks::InputCallbacks;
+pub use data::InputChanges;
 pub use data::InputData;
 pub use data::InputDataWithId;
+pub use data::InputSnapshot;
+pub use players::PlayerAssignments;
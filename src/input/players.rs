@@ -0,0 +1,115 @@
+use ahash::AHashMap;
+use winit::event::DeviceId;
+
+use crate::EventHelper;
+
+#[derive(Clone, Debug, Default)]
+/// Assigns input devices to player slots, e.g. for local multiplayer.
+///
+/// Devices are assigned as they press a key or button,
+/// see [CallbackData::pressed_devices](crate::callbacks::CallbackData::pressed_devices).
+pub struct PlayerAssignments {
+    players: AHashMap<usize, DeviceId>,
+}
+
+impl PlayerAssignments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assigns the first device that pressed a key or button this step
+    /// and is not assigned to another player to the given player.
+    ///
+    /// Call this every step while waiting for the player to join.
+    /// Returns true if a device was assigned.
+    pub fn assign_on_input<D>(&mut self, event_helper: &EventHelper<D>, player: usize) -> bool {
+        let device = event_helper
+            .data
+            .pressed_devices
+            .iter()
+            .copied()
+            .find(|device| self.player_for_device(*device).is_none());
+
+        if let Some(device) = device {
+            self.players.insert(player, device);
+        }
+
+        device.is_some()
+    }
+
+    /// Assigns the given device to the given player, replacing any previous assignment of either
+    pub fn assign(&mut self, player: usize, device: DeviceId) {
+        self.players.retain(|_, assigned| *assigned != device);
+        self.players.insert(player, device);
+    }
+
+    /// Removes the device assignment of the given player, returning the device if it existed
+    pub fn unassign(&mut self, player: usize) -> Option<DeviceId> {
+        self.players.remove(&player)
+    }
+
+    pub fn device_for_player(&self, player: usize) -> Option<DeviceId> {
+        self.players.get(&player).copied()
+    }
+
+    pub fn player_for_device(&self, device: DeviceId) -> Option<usize> {
+        self.players
+            .iter()
+            .find(|(_, assigned)| **assigned == device)
+            .map(|(player, _)| *player)
+    }
+
+    pub fn clear(&mut self) {
+        self.players.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::{ElementState, VirtualKeyCode};
+
+    use super::*;
+    use crate::{testing, Callbacks};
+
+    #[test]
+    fn devices_are_assigned_to_one_player_at_a_time() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+        let mut players = PlayerAssignments::new();
+
+        testing::step(&mut eh, &callbacks);
+        assert!(!players.assign_on_input(&eh, 0));
+
+        testing::feed_device_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::A,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+        assert!(players.assign_on_input(&eh, 0));
+        let device = players.device_for_player(0).unwrap();
+        assert_eq!(players.player_for_device(device), Some(0));
+
+        // the device is taken, so it does not join as another player
+        testing::feed_device_key(
+            &mut eh,
+            &callbacks,
+            VirtualKeyCode::B,
+            ElementState::Pressed,
+        );
+        testing::step(&mut eh, &callbacks);
+        assert!(!players.assign_on_input(&eh, 1));
+
+        players.assign(1, device);
+        assert_eq!(players.device_for_player(0), None);
+        assert_eq!(players.player_for_device(device), Some(1));
+
+        assert_eq!(players.unassign(1), Some(device));
+        assert_eq!(players.player_for_device(device), None);
+
+        players.assign(2, device);
+        players.clear();
+        assert_eq!(players.device_for_player(2), None);
+    }
+}
@@ -14,7 +14,7 @@
 //!
 //! ## Example
 //!
-//! ```rust
+//! ```rust,no_run
 //! use winit::event_loop::{ControlFlow, EventLoop};
 //! use winit::window::WindowBuilder;
 //! use winit_event_helper::*;
@@ -65,6 +65,9 @@
 //!
 //! For a complete overview of functions, see [callbacks].
 //!
+//! Callbacks are function pointers by default.
+//! Enable the `boxed_callbacks` feature to register closures that capture their environment instead.
+//!
 //! ## Keyboard and Mouse Inputs
 //!
 //! Keyboard and mouse inputs are combined and moved into the [InputData](input::InputData) struct.
@@ -74,6 +77,11 @@
 //!
 //! Callbacks are collected in [InputCallbacks](input::InputCallbacks).
 //!
+//! ## Touches
+//!
+//! Touches are tracked per finger across steps in the [TouchData](touch::TouchData) struct,
+//! which can be accessed as field `touches` on the [WindowCallbackData](crate::callbacks::WindowCallbackData) struct.
+//!
 //! ## Features
 #![doc = document_features::document_features!()]
 
@@ -82,10 +90,21 @@ pub mod default_ahashmap;
 pub mod definitions;
 pub mod event_helper;
 pub mod input;
+pub mod recording;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod touch;
 
 #[macro_use]
 mod macros;
 
+#[doc(hidden)]
+/// Dependencies used by the exported macros, so crates using them do not need to depend on these.
+pub mod __private {
+    pub use ahash;
+    pub use paste;
+}
+
 pub use crate::callbacks::all::Callbacks;
 pub use crate::definitions::*;
 pub use crate::event_helper::EventHelper;
@@ -11,14 +11,105 @@ macro_rules! __callback_data_type {
         Vec<$Type>
     };
     (set $Type:ty) => {
-        ahash::AHashSet<$Type>
+        $crate::__private::ahash::AHashSet<$Type>
     };
     (map $Type:ty, $Type2:ty) => {
-        ahash::AHashMap<$Type, $Type2>
+        $crate::__private::ahash::AHashMap<$Type, $Type2>
     };
     (cus $Type:ty) => {
         $Type
     };
+    (dat $Type:ty) => {
+        $Type
+    };
+}
+
+#[cfg(not(feature = "boxed_callbacks"))]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __callback_arg {
+    (CB<$D:ty>) => {
+        CB<$D>
+    };
+    (CBI<$D:ty, $Type:ty>) => {
+        CBI<$D, $Type>
+    };
+}
+
+#[cfg(feature = "boxed_callbacks")]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __callback_arg {
+    (CB<$D:ty>) => {
+        impl FnMut(&mut EventHelper<$D>) + 'static
+    };
+    (CBI<$D:ty, $Type:ty>) => {
+        impl FnMut(&mut EventHelper<$D>, $Type) + 'static
+    };
+}
+
+#[cfg(not(feature = "boxed_callbacks"))]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __store_callback {
+    ($callback:expr) => {
+        $callback
+    };
+}
+
+#[cfg(feature = "boxed_callbacks")]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __store_callback {
+    ($callback:expr) => {
+        ::std::cell::RefCell::new(::std::boxed::Box::new($callback))
+    };
+}
+
+#[cfg(not(feature = "boxed_callbacks"))]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __invoke_callback {
+    ($callback:expr, $($arg:expr),*) => {
+        ($callback)($($arg),*)
+    };
+}
+
+#[cfg(feature = "boxed_callbacks")]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __invoke_callback {
+    ($callback:expr, $($arg:expr),*) => {
+        ($callback.borrow_mut())($($arg),*)
+    };
+}
+
+// Selected here instead of inside `create_callbacks`,
+// as a `cfg` in the expansion would check the features of the invoking crate.
+#[cfg(not(feature = "boxed_callbacks"))]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __impl_callbacks_clone {
+    ($Callbacks:ident { $($(#[$outer_param:meta])* $param:ident),* }) => {
+        impl<D> Clone for $Callbacks<D> {
+            #[allow(unused_doc_comments)]
+            fn clone(&self) -> Self {
+                Self {
+                    $(
+                        $(#[$outer_param])*
+                        $param: self.$param.clone()
+                    ),*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "boxed_callbacks")]
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __impl_callbacks_clone {
+    ($($t:tt)*) => {};
 }
 
 #[macro_export(local_inner_macros)]
@@ -26,49 +117,51 @@ macro_rules! __callback_data_type {
 macro_rules! __call_callback {
     (boo, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {
         if $self.$param {
-            ($callbacks.$param)($event_helper);
+            __invoke_callback!($callbacks.$param, $event_helper);
         }
     };
     (opt, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {
         if let Some(value) = $self.$param.clone() {
-            ($callbacks.$param)($event_helper, value);
+            __invoke_callback!($callbacks.$param, $event_helper, value);
         }
     };
-    (vec, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {{
-        let vector = $self.$param.clone();
-        if !vector.is_empty() {
-            ($callbacks.$param)($event_helper, vector);
+    // Empty collections are not cloned, as a cleared collection keeps its capacity,
+    // which a clone would allocate again every step.
+    (vec, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {
+        if !$self.$param.is_empty() {
+            __invoke_callback!($callbacks.$param, $event_helper, $self.$param.clone());
         }
-    }};
-    (set, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {{
-        let set = $self.$param.clone();
+    };
+    (set, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {
+        if !$self.$param.is_empty() {
+            let set = $self.$param.clone();
 
-        set.iter().for_each(|key| {
-            $callbacks.$param.1.get(key).map(|func| func($event_helper));
-        });
+            set.iter().for_each(|key| {
+                $callbacks.$param.1.get(key).map(|func| __invoke_callback!(func, $event_helper));
+            });
 
-        if !set.is_empty() {
-            ($callbacks.$param.0)($event_helper, set);
+            __invoke_callback!($callbacks.$param.0, $event_helper, set);
         }
-    }};
-    (map, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {{
-        let map = $self.$param.clone();
-
-        map.iter().for_each(|(key, value)| {
-            $callbacks
-                .$param
-                .1
-                .get(&key)
-                .map(|func| func($event_helper, value.clone()));
-        });
-
-        if !map.is_empty() {
-            ($callbacks.$param.0)($event_helper, map);
+    };
+    (map, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {
+        if !$self.$param.is_empty() {
+            let map = $self.$param.clone();
+
+            map.iter().for_each(|(key, value)| {
+                $callbacks
+                    .$param
+                    .1
+                    .get(&key)
+                    .map(|func| __invoke_callback!(func, $event_helper, value.clone()));
+            });
+
+            __invoke_callback!($callbacks.$param.0, $event_helper, map);
         }
-    }};
+    };
     (cus, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {
         CallbackCallable::call_callbacks(&$self.$param, $event_helper, &$callbacks.$param);
     };
+    (dat, $self:ident, $event_helper:ident, $callbacks:ident, $param:ident) => {};
 }
 
 #[macro_export(local_inner_macros)]
@@ -104,8 +197,8 @@ macro_rules! __get_value {
             &self.$param
         }
 
-        $(#[$outer_param])*
-        paste::paste! {
+        $crate::__private::paste::paste! {
+            $(#[$outer_param])*
             pub fn [<$param _with_key>](&self, key: &$Type) -> &Option<$Type2> {
                 self.$param.get(key)
             }
@@ -141,6 +234,26 @@ macro_rules! __clear_value {
     (clr cus $self:ident $param:ident) => {
         $self.$param.clear();
     };
+    // `clone_from` keeps the allocation of e.g. a `Vec`, unlike assigning the default.
+    (clr dat $self:ident $param:ident) => {
+        $self.$param.clone_from(&Default::default());
+    };
+}
+
+// The derived `clone_from` of `AHashMap` and `AHashSet` allocates a new map,
+// while the one of the wrapped std map reuses the existing allocation.
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! __clone_value_from {
+    (set $self:ident $source:ident $param:ident) => {
+        (*$self.$param).clone_from(&$source.$param);
+    };
+    (map $self:ident $source:ident $param:ident) => {
+        (*$self.$param).clone_from(&$source.$param);
+    };
+    ($type_kw:ident $self:ident $source:ident $param:ident) => {
+        $self.$param.clone_from(&$source.$param);
+    };
 }
 
 #[macro_export(local_inner_macros)]
@@ -156,35 +269,39 @@ macro_rules! __callback_type {
         CBI<D, Vec<$Type>>
     };
     (set $Type:ty) => {
-        (CBI<D, ahash::AHashSet<$Type>>, ahash::AHashMap<$Type, CB<D>>)
+        (CBI<D, $crate::__private::ahash::AHashSet<$Type>>, $crate::__private::ahash::AHashMap<$Type, CB<D>>)
     };
     (map $Type:ty, $Type2:ty) => {
-        (CBI<D, ahash::AHashMap<$Type, $Type2>>, ahash::AHashMap<$Type, CBI<D, $Type2>>)
+        (CBI<D, $crate::__private::ahash::AHashMap<$Type, $Type2>>, $crate::__private::ahash::AHashMap<$Type, CBI<D, $Type2>>)
     };
     (cus $Type:ty) => { <$Type as CallbackCallable<D>>::CallbackStruct };
+    (dat $Type:ty) => { () };
 }
 
 #[macro_export(local_inner_macros)]
 #[doc(hidden)]
 macro_rules! __callback_type_default {
     (boo) => {
-        |_| {}
+        __store_callback!(|_| {})
     };
     (opt) => {
-        |_, _| {}
+        __store_callback!(|_, _| {})
     };
     (vec) => {
-        |_, _| {}
+        __store_callback!(|_, _| {})
     };
     (set) => {
-        (|_, _| {}, Default::default())
+        (__store_callback!(|_, _| {}), Default::default())
     };
     (map) => {
-        (|_, _| {}, Default::default())
+        (__store_callback!(|_, _| {}), Default::default())
     };
     (cus) => {
         Default::default()
     };
+    (dat) => {
+        ()
+    };
 }
 
 #[macro_export(local_inner_macros)]
@@ -192,49 +309,50 @@ macro_rules! __callback_type_default {
 macro_rules! __define_callback_func {
     ($(#[$outer_param:meta])*, boo, $param:ident: $Type:ty) => {
         $(#[$outer_param])*
-        pub fn $param(&mut self, callback: CB<D>) {
-            self.$param = callback;
+        pub fn $param(&mut self, callback: __callback_arg!(CB<D>)) {
+            self.$param = __store_callback!(callback);
         }
     };
     ($(#[$outer_param:meta])*, opt, $param:ident: $Type:ty) => {
         $(#[$outer_param])*
-        pub fn $param(&mut self, callback: CBI<D, $Type>) {
-            self.$param = callback;
+        pub fn $param(&mut self, callback: __callback_arg!(CBI<D, $Type>)) {
+            self.$param = __store_callback!(callback);
         }
     };
     ($(#[$outer_param:meta])*, vec, $param:ident: $Type:ty) => {
         $(#[$outer_param])*
-        pub fn $param(&mut self, callback: CBI<D, Vec<$Type>>) {
-            self.$param = callback;
+        pub fn $param(&mut self, callback: __callback_arg!(CBI<D, Vec<$Type>>)) {
+            self.$param = __store_callback!(callback);
         }
     };
     ($(#[$outer_param:meta])*, set, $param:ident: $Type:ty) => {
         $(#[$outer_param])*
-        pub fn $param(&mut self, callback: CBI<D, ahash::AHashSet<$Type>>) {
-            self.$param.0 = callback;
+        pub fn $param(&mut self, callback: __callback_arg!(CBI<D, $crate::__private::ahash::AHashSet<$Type>>)) {
+            self.$param.0 = __store_callback!(callback);
         }
 
-        $(#[$outer_param])*
-        paste::paste! {
-            pub fn [<$param _with_key>](&mut self, key: $Type, callback: CB<D>) {
-                self.$param.1.insert(key, callback);
+        $crate::__private::paste::paste! {
+            $(#[$outer_param])*
+            pub fn [<$param _with_key>](&mut self, key: $Type, callback: __callback_arg!(CB<D>)) {
+                self.$param.1.insert(key, __store_callback!(callback));
             }
         }
     };
     ($(#[$outer_param:meta])*, map, $param:ident: $Type:ty, $Type2:ty) => {
         $(#[$outer_param])*
-        pub fn $param(&mut self, callback: CBI<D, ahash::AHashMap<$Type, $Type2>>) {
-            self.$param.0 = callback;
+        pub fn $param(&mut self, callback: __callback_arg!(CBI<D, $crate::__private::ahash::AHashMap<$Type, $Type2>>)) {
+            self.$param.0 = __store_callback!(callback);
         }
 
-        $(#[$outer_param])*
-        paste::paste! {
-            pub fn [<$param _with_key>](&mut self, key: $Type, callback: CBI<D, $Type2>) {
-                self.$param.1.insert(key, callback);
+        $crate::__private::paste::paste! {
+            $(#[$outer_param])*
+            pub fn [<$param _with_key>](&mut self, key: $Type, callback: __callback_arg!(CBI<D, $Type2>)) {
+                self.$param.1.insert(key, __store_callback!(callback));
             }
         }
     };
     ($(#[$outer_param:meta])*, cus, $param:ident: $Type:ty) => {};
+    ($(#[$outer_param:meta])*, dat, $param:ident: $Type:ty) => {};
 }
 
 /// Creates a callback struct based on the captured struct's fields
@@ -254,9 +372,79 @@ macro_rules! __define_callback_func {
 /// - `vec` (Vec)
 /// - `opt` (Option)
 /// - `boo` (no wrapping, used for boolean switches)
-/// - `cus` (custom type, expected to implement the `call_callbacks(&self)` function)
+/// - `cus` (custom type implementing [CallbackCallable](crate::definitions::CallbackCallable))
+/// - `dat` (no wrapping, plain data without a callback)
+///
+/// The macro imports [EventHelper](crate::EventHelper), [CallbackCallable](crate::definitions::CallbackCallable),
+/// [CB](crate::definitions::CB) and [CBI](crate::definitions::CBI) into the module it is invoked in,
+/// so those should not be imported there again.
+///
+/// # Custom data
+///
+/// A `cus` field holds its own data and callbacks, which allows plugging accumulating data
+/// into the generated structs, like [InputData](crate::input::InputData) does.
+/// Its type has to implement [Clone], [Default] and [CallbackCallable](crate::definitions::CallbackCallable),
+/// the `CallbackStruct` of which is used as the field type of the generated callback struct
+/// and has to implement [Default], and [Clone] without the `boxed_callbacks` feature.
+/// A `clr cus` field also needs a `clear(&mut self)` function, which is called by the generated `clear`.
+///
+/// The data is copied into a reused buffer with [Clone::clone_from] every step before the callbacks are called,
+/// so implementing `clone_from` such that it keeps existing allocations avoids allocating every step.
+///
+/// Callbacks are stored with [store_callback](crate::definitions::store_callback) and called with
+/// [invoke_callback](crate::definitions::invoke_callback), or the `_with` variants for callbacks with an input,
+/// which work both with and without the `boxed_callbacks` feature.
+///
+/// ```
+/// use winit_event_helper::{create_callbacks, invoke_callback_with, store_callback_with};
+///
+/// #[derive(Clone, Default)]
+/// pub struct Packets(Vec<String>);
+///
+/// impl Packets {
+///     pub fn receive(&mut self, packet: String) {
+///         self.0.push(packet);
+///     }
+///
+///     pub fn clear(&mut self) {
+///         self.0.clear();
+///     }
+/// }
+///
+/// impl<D> CallbackCallable<D> for Packets {
+///     type CallbackStruct = Vec<CBI<D, String>>;
+///
+///     fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {
+///         for packet in &self.0 {
+///             for callback in callbacks {
+///                 invoke_callback_with(callback, event_helper, packet.clone());
+///             }
+///         }
+///     }
+/// }
+///
+/// create_callbacks! {
+///     pub struct NetworkData: NetworkCallbacks<D> {
+///         clr cus pub packets: Packets,
+///         clr boo pub disconnected: bool,
+///     }
+/// }
+///
+/// let mut callbacks = NetworkCallbacks::<Vec<String>>::default();
+/// callbacks.disconnected(|eh| eh.clear());
+/// callbacks.packets.push(store_callback_with(|eh: &mut EventHelper<Vec<String>>, packet: String| {
+///     eh.push(packet)
+/// }));
+///
+/// let mut eh = EventHelper::new(vec![]);
+/// let mut data = NetworkData::default();
+/// data.packets.receive("hello".to_string());
+///
+/// data.call_callbacks(&mut eh, &callbacks);
+/// data.clear();
+/// assert_eq!(eh.user_data, ["hello"]);
+/// ```
 #[macro_export(local_inner_macros)]
-#[doc(hidden)]
 macro_rules! create_callbacks {
     (
         $(#[$outer:meta])*
@@ -269,10 +457,10 @@ macro_rules! create_callbacks {
 
         $($t:tt)*
     ) => {
-        use crate::{event_helper::EventHelper, definitions::{CallbackCallable, CB, CBI}};
+        use $crate::{event_helper::EventHelper, definitions::{CallbackCallable, CB, CBI}};
 
         $(#[$outer])*
-        #[derive(Clone, Default)]
+        #[derive(Default)]
         $vis struct $CallbackData {
             $(
                 $(#[$outer_param])*
@@ -280,9 +468,30 @@ macro_rules! create_callbacks {
             ),*
         }
 
+        impl Clone for $CallbackData {
+            #[allow(unused_doc_comments)]
+            fn clone(&self) -> Self {
+                Self {
+                    $(
+                        $(#[$outer_param])*
+                        $param: self.$param.clone()
+                    ),*
+                }
+            }
+
+            #[allow(unused_doc_comments)]
+            fn clone_from(&mut self, source: &Self) {
+                $(
+                    $(#[$outer_param])*
+                    __clone_value_from!($type_kw self source $param);
+                )*
+            }
+        }
+
         impl<D> CallbackCallable<D> for $CallbackData {
             type CallbackStruct = $Callbacks<D>;
 
+            #[allow(unused_doc_comments)]
             fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &$Callbacks<D>) {
                 $(
                     $(#[$outer_param])*
@@ -293,6 +502,7 @@ macro_rules! create_callbacks {
 
         #[allow(dead_code)]
         impl $CallbackData {
+            #[allow(unused_doc_comments)]
             pub fn clear(&mut self) {
                 $(
                     $(#[$outer_param])*
@@ -316,18 +526,17 @@ macro_rules! create_callbacks {
             ),*
         }
 
-        impl<D> Clone for $Callbacks<D> {
-            fn clone(&self) -> Self {
-                Self {
-                    $(
-                        $(#[$outer_param])*
-                        $param: self.$param.clone()
-                    ),*
-                }
+        __impl_callbacks_clone! {
+            $Callbacks {
+                $(
+                    $(#[$outer_param])*
+                    $param
+                ),*
             }
         }
 
         impl<D> Default for $Callbacks<D> {
+            #[allow(unused_doc_comments)]
             fn default() -> Self {
                 Self {
                     $(
@@ -349,3 +558,24 @@ macro_rules! create_callbacks {
     };
     () => {};
 }
+
+/// Combines the given [Modifiers](crate::definitions::Modifiers) flags, e.g. for binding tables.
+///
+/// Can be used in constants.
+///
+/// ```
+/// use winit_event_helper::{modifiers, Modifiers};
+///
+/// const SAVE_AS: Modifiers = modifiers!(CTRL | SHIFT);
+/// assert_eq!(SAVE_AS, Modifiers::CTRL | Modifiers::SHIFT);
+/// assert_eq!(modifiers!(), Modifiers::empty());
+/// ```
+#[macro_export]
+macro_rules! modifiers {
+    () => {
+        $crate::definitions::Modifiers::empty()
+    };
+    ($($modifier:ident)|+) => {
+        $crate::definitions::Modifiers::from_bits_truncate(0 $(| $crate::definitions::Modifiers::$modifier.bits())+)
+    };
+}
@@ -0,0 +1,201 @@
+//! Events can be recorded with an [EventRecorder] and replayed with [crate::EventHelper::replay],
+//! e.g. for reproducing bugs or testing callbacks without a real event loop.
+
+use winit::{
+    dpi::PhysicalSize,
+    event::{Event, WindowEvent},
+    window::WindowId,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// An owned copy of a winit [Event].
+///
+/// [WindowEvent::ScaleFactorChanged] borrows the new inner size,
+/// so it is stored separately with the size the event ended with.
+pub enum RecordedEvent {
+    Event(Event<'static, ()>),
+    ScaleFactorChanged {
+        window_id: WindowId,
+        scale_factor: f64,
+        new_inner_size: PhysicalSize<u32>,
+    },
+}
+
+impl RecordedEvent {
+    /// Copies the given event, or returns `None` for [Event::UserEvent]
+    pub fn new<E>(event: &Event<'_, E>) -> Option<Self> {
+        let event = match event {
+            &Event::NewEvents(start_cause) => Event::NewEvents(start_cause),
+            &Event::WindowEvent {
+                window_id,
+                event:
+                    WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        ref new_inner_size,
+                    },
+            } => {
+                return Some(Self::ScaleFactorChanged {
+                    window_id,
+                    scale_factor,
+                    new_inner_size: **new_inner_size,
+                })
+            }
+            &Event::WindowEvent {
+                window_id,
+                ref event,
+            } => Event::WindowEvent {
+                window_id,
+                event: owned_window_event(event)?,
+            },
+            &Event::DeviceEvent {
+                device_id,
+                ref event,
+            } => Event::DeviceEvent {
+                device_id,
+                event: event.clone(),
+            },
+            Event::UserEvent(_) => return None,
+            Event::Suspended => Event::Suspended,
+            Event::Resumed => Event::Resumed,
+            Event::MainEventsCleared => Event::MainEventsCleared,
+            &Event::RedrawRequested(window_id) => Event::RedrawRequested(window_id),
+            Event::RedrawEventsCleared => Event::RedrawEventsCleared,
+            Event::LoopDestroyed => Event::LoopDestroyed,
+        };
+
+        Some(Self::Event(event))
+    }
+}
+
+#[allow(deprecated)]
+/// Copies the given window event, or returns `None` for [WindowEvent::ScaleFactorChanged]
+fn owned_window_event(event: &WindowEvent<'_>) -> Option<WindowEvent<'static>> {
+    Some(match *event {
+        WindowEvent::Resized(size) => WindowEvent::Resized(size),
+        WindowEvent::Moved(position) => WindowEvent::Moved(position),
+        WindowEvent::CloseRequested => WindowEvent::CloseRequested,
+        WindowEvent::Destroyed => WindowEvent::Destroyed,
+        WindowEvent::DroppedFile(ref path) => WindowEvent::DroppedFile(path.clone()),
+        WindowEvent::HoveredFile(ref path) => WindowEvent::HoveredFile(path.clone()),
+        WindowEvent::HoveredFileCancelled => WindowEvent::HoveredFileCancelled,
+        WindowEvent::ReceivedCharacter(c) => WindowEvent::ReceivedCharacter(c),
+        WindowEvent::Focused(is_focused) => WindowEvent::Focused(is_focused),
+        WindowEvent::KeyboardInput {
+            device_id,
+            input,
+            is_synthetic,
+        } => WindowEvent::KeyboardInput {
+            device_id,
+            input,
+            is_synthetic,
+        },
+        WindowEvent::ModifiersChanged(modifiers) => WindowEvent::ModifiersChanged(modifiers),
+        WindowEvent::Ime(ref ime) => WindowEvent::Ime(ime.clone()),
+        WindowEvent::CursorMoved {
+            device_id,
+            position,
+            modifiers,
+        } => WindowEvent::CursorMoved {
+            device_id,
+            position,
+            modifiers,
+        },
+        WindowEvent::CursorEntered { device_id } => WindowEvent::CursorEntered { device_id },
+        WindowEvent::CursorLeft { device_id } => WindowEvent::CursorLeft { device_id },
+        WindowEvent::MouseWheel {
+            device_id,
+            delta,
+            phase,
+            modifiers,
+        } => WindowEvent::MouseWheel {
+            device_id,
+            delta,
+            phase,
+            modifiers,
+        },
+        WindowEvent::MouseInput {
+            device_id,
+            state,
+            button,
+            modifiers,
+        } => WindowEvent::MouseInput {
+            device_id,
+            state,
+            button,
+            modifiers,
+        },
+        WindowEvent::TouchpadMagnify {
+            device_id,
+            delta,
+            phase,
+        } => WindowEvent::TouchpadMagnify {
+            device_id,
+            delta,
+            phase,
+        },
+        WindowEvent::SmartMagnify { device_id } => WindowEvent::SmartMagnify { device_id },
+        WindowEvent::TouchpadRotate {
+            device_id,
+            delta,
+            phase,
+        } => WindowEvent::TouchpadRotate {
+            device_id,
+            delta,
+            phase,
+        },
+        WindowEvent::TouchpadPressure {
+            device_id,
+            pressure,
+            stage,
+        } => WindowEvent::TouchpadPressure {
+            device_id,
+            pressure,
+            stage,
+        },
+        WindowEvent::AxisMotion {
+            device_id,
+            axis,
+            value,
+        } => WindowEvent::AxisMotion {
+            device_id,
+            axis,
+            value,
+        },
+        WindowEvent::Touch(touch) => WindowEvent::Touch(touch),
+        WindowEvent::ScaleFactorChanged { .. } => return None,
+        WindowEvent::ThemeChanged(theme) => WindowEvent::ThemeChanged(theme),
+        WindowEvent::Occluded(is_occluded) => WindowEvent::Occluded(is_occluded),
+    })
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Records owned copies of the events passed to [crate::EventHelper::update].
+///
+/// Attach one with [crate::EventHelper::start_recording]. User events are not recorded.
+pub struct EventRecorder {
+    events: Vec<RecordedEvent>,
+}
+
+impl EventRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a copy of the given event
+    pub fn record<E>(&mut self, event: &Event<'_, E>) {
+        self.events.extend(RecordedEvent::new(event));
+    }
+
+    /// Returns the recorded events in the order they were received
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    pub fn into_events(self) -> Vec<RecordedEvent> {
+        self.events
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
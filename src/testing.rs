@@ -0,0 +1,193 @@
+//! Helpers for driving an [EventHelper] without a window or event loop,
+//! e.g. for testing callbacks.
+//!
+//! The synthesized events use dummy window and device ids.
+
+use winit::{
+    dpi::PhysicalPosition,
+    event::{
+        DeviceEvent, DeviceId, ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode,
+        WindowEvent,
+    },
+    window::WindowId,
+};
+
+use crate::{callbacks::all::Callbacks, EventHelper};
+
+/// Passes the given window event to [EventHelper::update] and returns its result
+pub fn feed_window_event<D, E: PartialEq>(
+    eh: &mut EventHelper<D>,
+    callbacks: &Callbacks<D, E>,
+    event: WindowEvent,
+) -> bool {
+    eh.update(
+        callbacks,
+        &Event::WindowEvent {
+            // SAFETY: the dummy id is never passed to a winit function
+            window_id: unsafe { WindowId::dummy() },
+            event,
+        },
+    )
+}
+
+/// Passes the given device event to [EventHelper::update] and returns its result
+pub fn feed_device_event<D, E: PartialEq>(
+    eh: &mut EventHelper<D>,
+    callbacks: &Callbacks<D, E>,
+    event: DeviceEvent,
+) -> bool {
+    eh.update(
+        callbacks,
+        &Event::DeviceEvent {
+            device_id: dummy_device_id(),
+            event,
+        },
+    )
+}
+
+/// Passes a keyboard input for the given key to [EventHelper::update]
+#[allow(deprecated)]
+pub fn feed_key<D, E: PartialEq>(
+    eh: &mut EventHelper<D>,
+    callbacks: &Callbacks<D, E>,
+    key: VirtualKeyCode,
+    state: ElementState,
+) -> bool {
+    feed_window_event(
+        eh,
+        callbacks,
+        WindowEvent::KeyboardInput {
+            device_id: dummy_device_id(),
+            input: KeyboardInput {
+                scancode: 0,
+                state,
+                virtual_keycode: Some(key),
+                modifiers: Default::default(),
+            },
+            is_synthetic: false,
+        },
+    )
+}
+
+/// Passes a device keyboard input for the given key to [EventHelper::update]
+#[allow(deprecated)]
+pub fn feed_device_key<D, E: PartialEq>(
+    eh: &mut EventHelper<D>,
+    callbacks: &Callbacks<D, E>,
+    key: VirtualKeyCode,
+    state: ElementState,
+) -> bool {
+    feed_device_event(
+        eh,
+        callbacks,
+        DeviceEvent::Key(KeyboardInput {
+            scancode: 0,
+            state,
+            virtual_keycode: Some(key),
+            modifiers: Default::default(),
+        }),
+    )
+}
+
+/// Passes a mouse input for the given button to [EventHelper::update]
+#[allow(deprecated)]
+pub fn feed_mouse<D, E: PartialEq>(
+    eh: &mut EventHelper<D>,
+    callbacks: &Callbacks<D, E>,
+    button: MouseButton,
+    state: ElementState,
+) -> bool {
+    feed_window_event(
+        eh,
+        callbacks,
+        WindowEvent::MouseInput {
+            device_id: dummy_device_id(),
+            state,
+            button,
+            modifiers: Default::default(),
+        },
+    )
+}
+
+/// Passes a cursor movement to the given position to [EventHelper::update]
+#[allow(deprecated)]
+pub fn feed_cursor<D, E: PartialEq>(
+    eh: &mut EventHelper<D>,
+    callbacks: &Callbacks<D, E>,
+    position: PhysicalPosition<f64>,
+) -> bool {
+    feed_window_event(
+        eh,
+        callbacks,
+        WindowEvent::CursorMoved {
+            device_id: dummy_device_id(),
+            position,
+            modifiers: Default::default(),
+        },
+    )
+}
+
+/// Passes [Event::MainEventsCleared] to [EventHelper::update], ending the current step.
+///
+/// Returns `true` if the callbacks were called.
+pub fn step<D, E: PartialEq>(eh: &mut EventHelper<D>, callbacks: &Callbacks<D, E>) -> bool {
+    eh.update(callbacks, &Event::MainEventsCleared)
+}
+
+fn dummy_device_id() -> DeviceId {
+    // SAFETY: the dummy id is never passed to a winit function
+    unsafe { DeviceId::dummy() }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::{ElementState, MouseButton, VirtualKeyCode};
+
+    use super::*;
+
+    #[test]
+    fn feed_key_and_step_activate_callbacks() {
+        let mut eh = EventHelper::new(0);
+        let mut callbacks = Callbacks::<i32>::empty();
+        callbacks
+            .window
+            .inputs
+            .just_pressed(VirtualKeyCode::Space, |eh| **eh += 1);
+
+        assert!(!feed_key(&mut eh, &callbacks, VirtualKeyCode::Space, ElementState::Pressed));
+        assert_eq!(*eh, 0);
+        assert!(step(&mut eh, &callbacks));
+        assert_eq!(*eh, 1);
+
+        // not just pressed anymore
+        step(&mut eh, &callbacks);
+        assert_eq!(*eh, 1);
+        assert!(eh.data.window.inputs.pressed(VirtualKeyCode::Space));
+    }
+
+    #[test]
+    fn feed_mouse_registers_press_and_release() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        feed_mouse(&mut eh, &callbacks, MouseButton::Left, ElementState::Pressed);
+        step(&mut eh, &callbacks);
+        assert!(eh.data.window.inputs.just_pressed(MouseButton::Left));
+
+        feed_mouse(&mut eh, &callbacks, MouseButton::Left, ElementState::Released);
+        step(&mut eh, &callbacks);
+        assert!(eh.data.window.inputs.just_released(MouseButton::Left));
+        assert!(!eh.data.window.inputs.pressed(MouseButton::Left));
+    }
+
+    #[test]
+    fn feed_device_key_updates_device_inputs() {
+        let mut eh = EventHelper::new(());
+        let callbacks = Callbacks::<()>::empty();
+
+        feed_device_key(&mut eh, &callbacks, VirtualKeyCode::A, ElementState::Pressed);
+        step(&mut eh, &callbacks);
+        assert!(eh.data.device.inputs.just_pressed(VirtualKeyCode::A));
+        assert!(!eh.data.window.inputs.pressed(VirtualKeyCode::A));
+    }
+}
@@ -0,0 +1,39 @@
+use ahash::AHashMap;
+
+use crate::definitions::{SwipeDirection, CB};
+
+#[cfg(feature = "boxed_callbacks")]
+use crate::EventHelper;
+
+/// A storage medium for touch callbacks.
+pub struct TouchCallbacks<D> {
+    pub swipes: AHashMap<SwipeDirection, CB<D>>,
+}
+
+#[cfg(not(feature = "boxed_callbacks"))]
+impl<D> Clone for TouchCallbacks<D> {
+    fn clone(&self) -> Self {
+        Self {
+            swipes: self.swipes.clone(),
+        }
+    }
+}
+
+impl<D> Default for TouchCallbacks<D> {
+    fn default() -> Self {
+        Self {
+            swipes: Default::default(),
+        }
+    }
+}
+
+impl<D> TouchCallbacks<D> {
+    /// Adds a callback that will activate when a single finger swiped in the given direction,
+    /// overwriting any previous callback for the same direction.
+    ///
+    /// See [TouchData::swipe](crate::touch::TouchData::swipe).
+    pub fn swipe(&mut self, direction: SwipeDirection, callback: crate::__callback_arg!(CB<D>)) {
+        self.swipes
+            .insert(direction, crate::__store_callback!(callback));
+    }
+}
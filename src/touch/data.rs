@@ -0,0 +1,243 @@
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use winit::{dpi::PhysicalPosition, event::TouchPhase};
+
+use crate::{
+    definitions::{CallbackCallable, IdLessTouch, SwipeDirection},
+    event_helper::EventHelper,
+};
+
+use super::callbacks::TouchCallbacks;
+
+/// The default minimum distance in physical pixels a finger has to travel to count as a swipe.
+pub const DEFAULT_SWIPE_MIN_DISTANCE: f64 = 50.0;
+
+/// The default minimum speed in physical pixels per second a single finger has to travel at to count as a swipe.
+pub const DEFAULT_SWIPE_MIN_SPEED: f64 = 500.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The path of a single finger from touching the screen until it was lifted.
+pub struct TouchStroke {
+    pub id: u64,
+    pub start: PhysicalPosition<f64>,
+    pub end: PhysicalPosition<f64>,
+    pub duration: Duration,
+}
+
+impl TouchStroke {
+    /// Returns the distance between the start and end of the stroke
+    pub fn distance(&self) -> f64 {
+        (self.end.x - self.start.x).hypot(self.end.y - self.start.y)
+    }
+
+    /// Returns the average speed of the stroke in physical pixels per second
+    pub fn speed(&self) -> f64 {
+        self.distance() / self.duration.as_secs_f64()
+    }
+
+    /// Returns the direction of the stroke, or `None` if it is shorter than `min_distance`
+    pub fn swipe_direction(&self, min_distance: f64) -> Option<SwipeDirection> {
+        (self.distance() >= min_distance).then(|| {
+            SwipeDirection::from_delta(self.end.x - self.start.x, self.end.y - self.start.y)
+        })
+    }
+}
+
+/// A collection of touch data tracked per finger.
+pub struct TouchData {
+    /// The start position and instant of every finger currently touching the screen.
+    starts: AHashMap<u64, (PhysicalPosition<f64>, Instant)>,
+    /// The latest touch of every finger currently touching the screen.
+    active: AHashMap<u64, IdLessTouch>,
+    /// The strokes of fingers that were lifted this step.
+    ended: Vec<TouchStroke>,
+    /// The highest number of fingers that touched the screen at once since it was last untouched.
+    gesture_fingers: usize,
+    swipe_min_distance: f64,
+    swipe_min_speed: f64,
+}
+
+impl<D> CallbackCallable<D> for TouchData {
+    type CallbackStruct = TouchCallbacks<D>;
+
+    fn call_callbacks(&self, event_helper: &mut EventHelper<D>, callbacks: &Self::CallbackStruct) {
+        if let Some(func) = self
+            .swipe()
+            .and_then(|direction| callbacks.swipes.get(&direction))
+        {
+            crate::__invoke_callback!(func, event_helper);
+        }
+    }
+}
+
+impl Clone for TouchData {
+    fn clone(&self) -> Self {
+        Self {
+            starts: self.starts.clone(),
+            active: self.active.clone(),
+            ended: self.ended.clone(),
+            gesture_fingers: self.gesture_fingers,
+            swipe_min_distance: self.swipe_min_distance,
+            swipe_min_speed: self.swipe_min_speed,
+        }
+    }
+
+    /// Reuses the allocations of `self`.
+    fn clone_from(&mut self, source: &Self) {
+        (*self.starts).clone_from(&source.starts);
+        (*self.active).clone_from(&source.active);
+        self.ended.clone_from(&source.ended);
+        self.gesture_fingers = source.gesture_fingers;
+        self.swipe_min_distance = source.swipe_min_distance;
+        self.swipe_min_speed = source.swipe_min_speed;
+    }
+}
+
+impl Default for TouchData {
+    fn default() -> Self {
+        Self {
+            starts: AHashMap::new(),
+            active: AHashMap::new(),
+            ended: Vec::new(),
+            gesture_fingers: 0,
+            swipe_min_distance: DEFAULT_SWIPE_MIN_DISTANCE,
+            swipe_min_speed: DEFAULT_SWIPE_MIN_SPEED,
+        }
+    }
+}
+
+impl TouchData {
+    pub fn update(&mut self, touch: IdLessTouch) {
+        match touch.phase {
+            TouchPhase::Started => {
+                if self.starts.is_empty() {
+                    self.gesture_fingers = 0;
+                }
+                self.starts
+                    .insert(touch.id, (touch.location, Instant::now()));
+                self.active.insert(touch.id, touch);
+                self.gesture_fingers = self.gesture_fingers.max(self.starts.len());
+            }
+            TouchPhase::Moved => {
+                if let Some(active) = self.active.get_mut(&touch.id) {
+                    *active = touch;
+                }
+            }
+            TouchPhase::Ended => {
+                self.active.remove(&touch.id);
+
+                if let Some((start, instant)) = self.starts.remove(&touch.id) {
+                    self.ended.push(TouchStroke {
+                        id: touch.id,
+                        start,
+                        end: touch.location,
+                        duration: instant.elapsed(),
+                    });
+                }
+            }
+            TouchPhase::Cancelled => {
+                self.starts.remove(&touch.id);
+                self.active.remove(&touch.id);
+            }
+        }
+    }
+
+    /// Returns the latest touch of every finger currently touching the screen, keyed by finger id
+    pub fn active_touches(&self) -> &AHashMap<u64, IdLessTouch> {
+        &self.active
+    }
+
+    /// Returns the strokes of the fingers that were lifted this step
+    pub fn ended_strokes(&self) -> &[TouchStroke] {
+        &self.ended
+    }
+
+    /// Sets the minimum distance in physical pixels a finger has to travel to count as a swipe
+    pub fn set_swipe_min_distance(&mut self, distance: f64) {
+        self.swipe_min_distance = distance;
+    }
+
+    /// Sets the minimum speed in physical pixels per second a single finger has to travel at to count as a swipe
+    pub fn set_swipe_min_speed(&mut self, speed: f64) {
+        self.swipe_min_speed = speed;
+    }
+
+    /// Returns the direction if a single finger swiped and was lifted this step.
+    ///
+    /// The finger has to be the only one that touched the screen during the gesture,
+    /// and has to travel at least the minimum swipe distance at at least the minimum swipe speed.
+    pub fn swipe(&self) -> Option<SwipeDirection> {
+        let [stroke] = self.ended.as_slice() else {
+            return None;
+        };
+
+        if self.gesture_fingers != 1
+            || !self.starts.is_empty()
+            || stroke.speed() < self.swipe_min_speed
+        {
+            return None;
+        }
+
+        stroke.swipe_direction(self.swipe_min_distance)
+    }
+
+    /// Returns the number of fingers and their direction
+    /// if multiple fingers were lifted this step after all swiping in the same direction.
+    pub fn multi_finger_swipe(&self) -> Option<(u32, SwipeDirection)> {
+        if self.ended.len() < 2 {
+            return None;
+        }
+
+        let mut directions = self
+            .ended
+            .iter()
+            .map(|stroke| stroke.swipe_direction(self.swipe_min_distance));
+
+        let direction = directions.next()??;
+        directions
+            .all(|other| other == Some(direction))
+            .then_some((self.ended.len() as u32, direction))
+    }
+
+    /// Clears the strokes that ended this step
+    pub fn clear(&mut self) {
+        self.ended.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, x: f64, y: f64) -> IdLessTouch {
+        IdLessTouch {
+            phase,
+            location: PhysicalPosition::new(x, y),
+            force: None,
+            id,
+        }
+    }
+
+    #[test]
+    fn three_finger_swipe_right() {
+        let mut data = TouchData::default();
+
+        // three fingers stacked vertically, all moving right
+        for (phase, x) in [
+            (TouchPhase::Started, 100.0),
+            (TouchPhase::Moved, 160.0),
+            (TouchPhase::Ended, 220.0),
+        ] {
+            for id in 0..3 {
+                data.update(touch(id, phase, x, 100.0 + id as f64 * 40.0));
+            }
+        }
+
+        assert_eq!(data.multi_finger_swipe(), Some((3, SwipeDirection::Right)));
+        assert_eq!(data.swipe(), None);
+
+        data.clear();
+        assert_eq!(data.multi_finger_swipe(), None);
+    }
+}
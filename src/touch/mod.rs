@@ -0,0 +1,12 @@
+//! Touches are tracked per finger across steps in the [TouchData] struct.
+//!
+//! This can be accessed as field `touches` on the
+//! [WindowCallbackData](crate::callbacks::WindowCallbackData) struct.
+
+pub mod callbacks;
+pub mod data;
+
+pub use callbacks::TouchCallbacks;
+
+pub use data::TouchData;
+pub use data::TouchStroke;